@@ -0,0 +1,94 @@
+use crate::challenge::{self, Challenge};
+use crate::generate;
+use anyhow::{anyhow, Context};
+use std::time::Duration;
+
+const SEEDS: u64 = 5;
+const BAR_WIDTH: usize = 40;
+
+// Runs a challenge over generated inputs of growing size and plots the median duration of each
+// size, along with the growth between sizes as an exponent, e.g. n^2 for quadratic scaling
+pub fn run(mut args: impl Iterator<Item = String>) -> anyhow::Result<()> {
+    let challenge = args
+        .next()
+        .context("Missing challenge, expecting benchmark <challenge> <size>...")?
+        .parse()?;
+
+    let sizes = args
+        .map(|arg| arg.parse().with_context(|| format!("Invalid size {}", arg)))
+        .collect::<anyhow::Result<Vec<usize>>>()?;
+
+    if sizes.is_empty() {
+        return Err(anyhow!(
+            "Missing sizes, expecting benchmark <challenge> <size>..."
+        ));
+    }
+
+    let durations = sizes
+        .iter()
+        .map(|size| measure(&challenge, *size))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    println!("{}, median of {} generated inputs", challenge, SEEDS);
+
+    let longest = durations.iter().max().copied().unwrap_or_default();
+
+    for (i, (size, duration)) in sizes.iter().zip(&durations).enumerate() {
+        let width = (duration.as_secs_f64() / longest.as_secs_f64() * BAR_WIDTH as f64).round();
+        let bar = "#".repeat(width as usize);
+
+        let growth = match i.checked_sub(1) {
+            Some(previous) => growth(sizes[previous], durations[previous], *size, *duration)
+                .map(|exponent| format!("n^{:.2}", exponent))
+                .unwrap_or_default(),
+            None => String::new(),
+        };
+
+        println!(
+            "{:>10} {:>12} {:<width$} {}",
+            size,
+            format!("{:.1?}", duration),
+            bar,
+            growth,
+            width = BAR_WIDTH
+        );
+    }
+
+    Ok(())
+}
+
+fn measure(challenge: &Challenge, size: usize) -> anyhow::Result<Duration> {
+    let mut durations = (0..SEEDS)
+        .map(|seed| {
+            let input = generate::generate(challenge.day(), size, seed);
+            let answer = challenge::run(challenge, &input).with_context(|| {
+                format!(
+                    "{} failed on the input of size {} and seed {}",
+                    challenge, size, seed
+                )
+            })?;
+
+            Ok(answer.duration)
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    durations.sort();
+    Ok(durations[durations.len() / 2])
+}
+
+// The exponent k for which duration grows like size^k between the two points
+fn growth(
+    size: usize,
+    duration: Duration,
+    next_size: usize,
+    next_duration: Duration,
+) -> Option<f64> {
+    let sizes = next_size as f64 / size as f64;
+    let durations = next_duration.as_secs_f64() / duration.as_secs_f64();
+
+    if sizes <= 1.0 || !durations.is_finite() || durations <= 0.0 {
+        return None;
+    }
+
+    Some(durations.ln() / sizes.ln())
+}
@@ -0,0 +1,66 @@
+use crate::challenge::{self, Day, Plane, Seat};
+use anyhow::{anyhow, Context};
+
+// Decodes boarding passes into their seats, encodes seats given as an ID or as row,column into
+// boarding passes, or analyzes which seats the passes of an input take. The plane is of the day 5
+// size unless --rows and --columns give other bit counts.
+pub async fn run(mut args: impl Iterator<Item = String>) -> anyhow::Result<()> {
+    const USAGE: &str = "expecting boarding [--rows <bits>] [--columns <bits>] \
+                         decode <pass>... | encode <id|row,column>... | analyze [--input <file>]";
+
+    let mut row_bits = 7;
+    let mut column_bits = 3;
+    let mut command = None;
+    let mut path = None;
+    let mut values = Vec::new();
+
+    while let Some(arg) = args.next() {
+        let mut bits = || -> anyhow::Result<u32> {
+            let value = args
+                .next()
+                .with_context(|| format!("Missing value for {}", arg))?;
+            value
+                .parse()
+                .with_context(|| format!("Invalid value for {}", arg))
+        };
+
+        match arg.as_str() {
+            "--rows" => row_bits = bits()?,
+            "--columns" => column_bits = bits()?,
+            "--input" => path = Some(args.next().context("Missing value for --input")?),
+            _ if command.is_none() => command = Some(arg),
+            _ => values.push(arg),
+        }
+    }
+
+    let plane = Plane::new(row_bits, column_bits)?;
+    let command = command.with_context(|| format!("Missing command, {}", USAGE))?;
+
+    if command == "analyze" {
+        let input = challenge::read_input(Day::Day05, path.as_deref()).await?;
+        print!("{}", plane.analyze(&input)?);
+        return Ok(());
+    }
+
+    let seat = |value: &str| -> anyhow::Result<Seat> {
+        match command.as_str() {
+            "decode" => plane.decode(value),
+            "encode" => match value.split_once(',') {
+                Some((row, column)) => plane.seat(row.parse()?, column.parse()?),
+                None => plane.seat_with_id(value.parse()?),
+            },
+            _ => Err(anyhow!("Unknown command {}, {}", command, USAGE)),
+        }
+    };
+
+    if values.is_empty() {
+        return Err(anyhow!("Missing values to {}, {}", command, USAGE));
+    }
+
+    for value in &values {
+        let seat = seat(value).with_context(|| format!("Cannot {} {}", command, value))?;
+        println!("{}: {}", seat.pass(), seat);
+    }
+
+    Ok(())
+}
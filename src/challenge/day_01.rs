@@ -1,41 +1,96 @@
-use anyhow::anyhow;
+use super::k_sum::KSum;
+use super::parse;
+use anyhow::Context;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+const TARGET: u64 = 2020;
 
 pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let numbers = parse_numbers(input)?;
+    product_of_sum(input, 2)
+}
 
-    for (i, &x) in numbers[0..numbers.len() - 1].iter().enumerate() {
-        let y = 2020 - x;
+// 61515678
+pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
+    product_of_sum(input, 3)
+}
 
-        if numbers[i + 1..].binary_search(&y).is_ok() {
-            return Ok(x * y);
-        }
-    }
+pub fn explain_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
+    audit(input, 2, "pair")
+}
 
-    Err(anyhow!("Could not find 2 values that sum up to 2020"))
+pub fn explain_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
+    audit(input, 3, "triple")
 }
 
-// 61515678
-pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let numbers = parse_numbers(input)?;
+// Audits the expense report: every combination of k values adding up to the target, traced back to
+// its lines. Anything but exactly one means the answer depends on which one is picked.
+fn audit(input: &[&str], k: usize, name: &str) -> anyhow::Result<String> {
+    let numbers = parse::lines::<u64>(input)?;
+    let combinations = KSum::new(k, TARGET).all(&numbers);
+    let mut output = String::new();
+
+    for combination in &combinations {
+        let lines = combination.indices.iter().map(|index| index + 1);
+        let values = combination.values.iter().map(u64::to_string);
 
-    for (i, &x) in numbers[0..numbers.len() - 2].iter().enumerate() {
-        for (j, &y) in numbers[i + 1..numbers.len() - 1].iter().enumerate() {
-            let z = 2020 - x - y;
+        let product = match combination.product() {
+            Some(product) => product.to_string(),
+            None => "too large".to_string(),
+        };
 
-            if numbers[i + j + 2..].binary_search(&z).is_ok() {
-                return Ok(x * y * z);
-            }
-        }
+        writeln!(
+            output,
+            "Lines {}: {} = {}, product {}",
+            enumerate(lines),
+            values.collect::<Vec<_>>().join(" + "),
+            TARGET,
+            product
+        )?;
     }
 
-    Err(anyhow!("Could not find 3 values that sum up to 2020"))
+    let products = combinations
+        .iter()
+        .map(|combination| combination.product())
+        .collect::<HashSet<_>>();
+
+    match combinations.len() {
+        0 => writeln!(
+            output,
+            "No {} adds up to {}, there is no answer",
+            name, TARGET
+        )?,
+        1 => writeln!(output, "1 {} adds up to {}", name, TARGET)?,
+        count => writeln!(
+            output,
+            "{} {}s add up to {} with {} distinct product{}, the input is ambiguous",
+            count,
+            name,
+            TARGET,
+            products.len(),
+            if products.len() == 1 { "" } else { "s" }
+        )?,
+    }
+
+    Ok(output)
 }
 
-fn parse_numbers(input: &[&str]) -> anyhow::Result<Vec<u32>> {
-    let mut numbers = input
-        .iter()
-        .map(|line| line.parse())
-        .collect::<Result<Vec<u32>, _>>()?;
-    numbers.sort_unstable();
-    Ok(numbers)
+fn product_of_sum(input: &[&str], k: usize) -> anyhow::Result<u64> {
+    let numbers = parse::lines::<u64>(input)?;
+
+    KSum::new(k, TARGET)
+        .first(&numbers)
+        .with_context(|| format!("Could not find {} values that sum up to {}", k, TARGET))?
+        .product()
+        .context("The product is too large")
+}
+
+// e.g. "2, 3 and 5"
+fn enumerate(items: impl Iterator<Item = impl std::fmt::Display>) -> String {
+    let items = items.map(|item| item.to_string()).collect::<Vec<_>>();
+
+    match items.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {}", rest.join(", "), last),
+        _ => items.concat(),
+    }
 }
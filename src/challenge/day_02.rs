@@ -1,37 +1,390 @@
-use anyhow::Context;
+use super::parse;
+use anyhow::{anyhow, Context};
+use std::fmt::Write;
+
+// Named policies which can be combined on the command line, the ones taking a number accept it
+// after `=`, e.g. `min-length=12`, and otherwise use the default
+const POLICIES: [(&str, Option<usize>, NewPolicy); 5] = [
+    ("count-in-range", None, |_| Box::new(CountInRange)),
+    ("positions", None, |_| Box::new(Positions)),
+    ("min-length", Some(8), |length| Box::new(MinLength(length))),
+    ("classes", Some(2), |count| {
+        Box::new(CharacterClasses(count))
+    }),
+    ("no-repeats", Some(2), |count| {
+        Box::new(ForbiddenRepeats(count))
+    }),
+];
+
+type NewPolicy = fn(usize) -> Box<dyn Policy>;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Parsing {
+    // Any malformed line is an error, which lists all of them
+    Strict,
+    // Malformed lines are skipped, and listed after the report
+    Lenient,
+}
 
 pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    Ok(count_valid(input, validate_a))
+    count_valid(input, &CountInRange, "count-in-range")
+}
+
+pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
+    count_valid(input, &Positions, "positions")
 }
 
-fn validate_a(entry: &Entry) -> bool {
-    let count = entry
-        .password
+// Checks every password against a combination of named policies, e.g.
+// `count-in-range and min-length=8 or positions`, where and binds tighter than or
+pub fn report(input: &[&str], expression: &str, parsing: Parsing) -> anyhow::Result<String> {
+    let policy = parse_policy(expression)?;
+    let entries = Entries::parse(input, parsing)?;
+    let mut output = String::new();
+    let mut valid = 0;
+
+    for (number, line, entry) in &entries.entries {
+        let verdict = if policy.complies(entry) {
+            valid += 1;
+            "valid".to_string()
+        } else {
+            format!("rejected by {}", policy.reason(entry))
+        };
+
+        writeln!(output, "Line {}, {}: {}", number, line, verdict)?;
+    }
+
+    writeln!(
+        output,
+        "{} of {} passwords are valid",
+        valid,
+        entries.entries.len()
+    )?;
+
+    if !entries.malformed.is_empty() {
+        writeln!(output, "Skipped {}", entries.malformed_summary())?;
+    }
+
+    Ok(output)
+}
+
+// Malformed lines are an error rather than skipped, as an answer which leaves out lines of a bad
+// download looks just as plausible as the right one. The report counts around them when asked to.
+fn count_valid(input: &[&str], policy: &dyn Policy, name: &str) -> anyhow::Result<usize> {
+    let entries = Entries::parse(input, Parsing::Strict).with_context(|| {
+        format!(
+            "Cannot count the valid passwords, `passwords --lenient {}` skips malformed lines",
+            name
+        )
+    })?;
+
+    Ok(entries
+        .entries
         .iter()
-        .filter(|&&x| x == entry.letter)
-        .take(entry.second + 1)
-        .count();
+        .filter(|(_, _, entry)| policy.complies(entry))
+        .count())
+}
 
-    count >= entry.first && count <= entry.second
+// The well-formed lines with their line numbers, and why the other lines are not
+struct Entries<'a> {
+    entries: Vec<(usize, &'a str, Entry<'a>)>,
+    malformed: Vec<(usize, anyhow::Error)>,
 }
 
-pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    Ok(count_valid(input, validate_b))
+impl<'a> Entries<'a> {
+    fn parse(input: &[&'a str], parsing: Parsing) -> anyhow::Result<Self> {
+        let mut entries = Vec::new();
+        let mut malformed = Vec::new();
+
+        for (i, &line) in input.iter().enumerate() {
+            match Entry::try_from(line) {
+                Ok(entry) => entries.push((i + 1, line, entry)),
+                Err(error) => malformed.push((i + 1, error)),
+            }
+        }
+
+        let entries = Entries { entries, malformed };
+
+        if parsing == Parsing::Strict && !entries.malformed.is_empty() {
+            return Err(anyhow!("{}", entries.malformed_summary()));
+        }
+
+        Ok(entries)
+    }
+
+    fn malformed_summary(&self) -> String {
+        let count = self.malformed.len();
+        let plural = if count == 1 { "" } else { "s" };
+        let mut summary = format!("{} malformed line{}", count, plural);
+
+        for (number, error) in &self.malformed {
+            write!(summary, "\n  line {}: {:#}", number, error).unwrap();
+        }
+
+        summary
+    }
+}
+
+// Counting the valid passwords only asks whether they comply, the reasons are only worked out for
+// the report
+trait Policy {
+    fn complies(&self, entry: &Entry) -> bool;
+
+    // Why the entry's password does not comply
+    fn reason(&self, entry: &Entry) -> String;
+}
+
+struct CountInRange;
+
+impl CountInRange {
+    fn occurrences<'a>(entry: &'a Entry) -> impl Iterator<Item = &'a u8> {
+        entry.password.iter().filter(|&&x| x == entry.letter)
+    }
+
+    // Stops once the letter occurs too often, as it is only compared against the range
+    fn count(entry: &Entry) -> usize {
+        Self::occurrences(entry).take(entry.second + 1).count()
+    }
 }
 
-fn validate_b(entry: &Entry) -> bool {
-    let first = entry.password[entry.first - 1] == entry.letter;
-    let second = entry.password[entry.second - 1] == entry.letter;
+impl Policy for CountInRange {
+    fn complies(&self, entry: &Entry) -> bool {
+        (entry.first..=entry.second).contains(&Self::count(entry))
+    }
 
-    first ^ second
+    fn reason(&self, entry: &Entry) -> String {
+        format!(
+            "{:?} occurs {} times, expecting {} to {}",
+            entry.letter as char,
+            Self::occurrences(entry).count(),
+            entry.first,
+            entry.second
+        )
+    }
 }
 
-fn count_valid(input: &[&str], predicate: impl Fn(&Entry) -> bool) -> usize {
-    input
+struct Positions;
+
+impl Positions {
+    // Positions start at 1, and one past the end of the password never holds the letter
+    fn holds_letter(entry: &Entry, position: usize) -> bool {
+        position
+            .checked_sub(1)
+            .and_then(|index| entry.password.get(index))
+            == Some(&entry.letter)
+    }
+}
+
+impl Policy for Positions {
+    fn complies(&self, entry: &Entry) -> bool {
+        Self::holds_letter(entry, entry.first) != Self::holds_letter(entry, entry.second)
+    }
+
+    fn reason(&self, entry: &Entry) -> String {
+        let verdict = if Self::holds_letter(entry, entry.first) {
+            "both"
+        } else {
+            "neither"
+        };
+
+        format!(
+            "{:?} is at {} of positions {} and {}",
+            entry.letter as char, verdict, entry.first, entry.second
+        )
+    }
+}
+
+struct MinLength(usize);
+
+impl Policy for MinLength {
+    fn complies(&self, entry: &Entry) -> bool {
+        entry.password.len() >= self.0
+    }
+
+    fn reason(&self, entry: &Entry) -> String {
+        format!(
+            "{} characters long, expecting at least {}",
+            entry.password.len(),
+            self.0
+        )
+    }
+}
+
+// Lowercase and uppercase letters, digits and anything else
+struct CharacterClasses(usize);
+
+impl CharacterClasses {
+    fn count(entry: &Entry) -> usize {
+        let classes: [fn(&u8) -> bool; 4] = [
+            u8::is_ascii_lowercase,
+            u8::is_ascii_uppercase,
+            u8::is_ascii_digit,
+            |x: &u8| !x.is_ascii_alphanumeric(),
+        ];
+
+        classes
+            .iter()
+            .filter(|class| entry.password.iter().any(class))
+            .count()
+    }
+}
+
+impl Policy for CharacterClasses {
+    fn complies(&self, entry: &Entry) -> bool {
+        Self::count(entry) >= self.0
+    }
+
+    fn reason(&self, entry: &Entry) -> String {
+        format!(
+            "uses {} character classes, expecting at least {}",
+            Self::count(entry),
+            self.0
+        )
+    }
+}
+
+// At most so many of the same character in a row
+struct ForbiddenRepeats(usize);
+
+impl ForbiddenRepeats {
+    fn find<'a>(&self, entry: &Entry<'a>) -> Option<&'a [u8]> {
+        entry
+            .password
+            .chunk_by(|a, b| a == b)
+            .find(|run| run.len() > self.0)
+    }
+}
+
+impl Policy for ForbiddenRepeats {
+    fn complies(&self, entry: &Entry) -> bool {
+        self.find(entry).is_none()
+    }
+
+    fn reason(&self, entry: &Entry) -> String {
+        let run = self.find(entry).unwrap_or_default();
+
+        format!(
+            "{:?} repeats {} times in a row, allowing at most {}",
+            run.first().copied().unwrap_or_default() as char,
+            run.len(),
+            self.0
+        )
+    }
+}
+
+// A policy from the registry, which names itself when it rejects a password
+struct Named {
+    name: String,
+    policy: Box<dyn Policy>,
+}
+
+impl Policy for Named {
+    fn complies(&self, entry: &Entry) -> bool {
+        self.policy.complies(entry)
+    }
+
+    fn reason(&self, entry: &Entry) -> String {
+        format!("{}, {}", self.name, self.policy.reason(entry))
+    }
+}
+
+struct All(Vec<Box<dyn Policy>>);
+
+impl Policy for All {
+    fn complies(&self, entry: &Entry) -> bool {
+        self.0.iter().all(|policy| policy.complies(entry))
+    }
+
+    // The first policy which rejects the password
+    fn reason(&self, entry: &Entry) -> String {
+        self.0
+            .iter()
+            .find(|policy| !policy.complies(entry))
+            .map(|policy| policy.reason(entry))
+            .unwrap_or_default()
+    }
+}
+
+struct Any(Vec<Box<dyn Policy>>);
+
+impl Policy for Any {
+    fn complies(&self, entry: &Entry) -> bool {
+        self.0.iter().any(|policy| policy.complies(entry))
+    }
+
+    // Every alternative rejects the password
+    fn reason(&self, entry: &Entry) -> String {
+        self.0
+            .iter()
+            .map(|policy| policy.reason(entry))
+            .collect::<Vec<_>>()
+            .join(" and by ")
+    }
+}
+
+fn parse_policy(expression: &str) -> anyhow::Result<Box<dyn Policy>> {
+    let tokens = expression.split_whitespace().collect::<Vec<_>>();
+
+    let alternatives = tokens
+        .split(|&token| token == "or")
+        .map(|terms| {
+            let policies = terms
+                .split(|&token| token == "and")
+                .map(|term| match term {
+                    [term] => named_policy(term),
+                    _ => Err(anyhow!(
+                        "Expected a single policy between and/or, found {:?}",
+                        term.join(" ")
+                    )),
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+
+            Ok(combine(policies, All))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok(combine(alternatives, Any))
+}
+
+fn combine<P: Policy + 'static>(
+    mut policies: Vec<Box<dyn Policy>>,
+    combinator: fn(Vec<Box<dyn Policy>>) -> P,
+) -> Box<dyn Policy> {
+    if policies.len() == 1 {
+        policies.remove(0)
+    } else {
+        Box::new(combinator(policies))
+    }
+}
+
+fn named_policy(term: &str) -> anyhow::Result<Box<dyn Policy>> {
+    let (name, argument) = match term.split_once('=') {
+        Some((name, argument)) => (name, Some(argument)),
+        None => (term, None),
+    };
+
+    let (_, default, policy) = POLICIES
         .iter()
-        .filter_map(|&line| Entry::try_from(line).ok())
-        .filter(predicate)
-        .count()
+        .find(|(candidate, ..)| *candidate == name)
+        .with_context(|| {
+            let names = POLICIES.map(|(name, ..)| name);
+            format!(
+                "Unknown policy {}, expecting one of {}",
+                name,
+                names.join(", ")
+            )
+        })?;
+
+    let argument = match (argument, default) {
+        (Some(argument), Some(_)) => parse::value(argument)?,
+        (None, Some(default)) => *default,
+        (Some(_), None) => return Err(anyhow!("{} does not take a number", name)),
+        (None, None) => 0,
+    };
+
+    Ok(Box::new(Named {
+        name: term.to_string(),
+        policy: policy(argument),
+    }))
 }
 
 struct Entry<'a> {
@@ -45,19 +398,20 @@ impl<'a> TryFrom<&'a str> for Entry<'a> {
     type Error = anyhow::Error;
 
     fn try_from(line: &'a str) -> Result<Self, Self::Error> {
-        let (first, line) = line
-            .split_once('-')
-            .context("Could not find range delimiter (`-`)")?;
+        let (first, line) = parse::split_once(line, "-")?;
+        let (second, line) = parse::split_once(line, " ")?;
+
+        let first = parse::value(first)?;
+        let second = parse::value(second)?;
 
-        let (second, line) = line
-            .split_once(' ')
-            .context("Could not find end of range (` `)")?;
+        let (letter, password) = parse::split_once(line, ": ")?;
 
-        let first = first.parse()?;
-        let second = second.parse()?;
+        let letter = match letter.as_bytes() {
+            [letter] => *letter,
+            _ => return Err(anyhow!("Expected a single letter, found {:?}", letter)),
+        };
 
-        let letter = line.as_bytes()[0];
-        let password = &line[3..].as_bytes();
+        let password = password.as_bytes();
 
         Ok(Entry {
             letter,
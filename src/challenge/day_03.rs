@@ -1,63 +1,180 @@
-pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    Ok(Map::new(input).simulate(3, 1))
+use super::grid::Grid;
+use super::{parse, LineIndex};
+use anyhow::anyhow;
+use std::str::FromStr;
+
+const SLOPES: [Slope; 5] = [
+    Slope { right: 1, down: 1 },
+    Slope { right: 3, down: 1 },
+    Slope { right: 5, down: 1 },
+    Slope { right: 7, down: 1 },
+    Slope { right: 1, down: 2 },
+];
+
+pub fn part_a(input: &[u8], lines: &LineIndex) -> anyhow::Result<impl std::fmt::Display> {
+    Ok(TobogganMap::new(input, lines)?.trees(SLOPES[1]))
 }
 
-pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let map = Map::new(input);
-    let mut product = 1usize;
+pub fn part_b(input: &[u8], lines: &LineIndex) -> anyhow::Result<impl std::fmt::Display> {
+    let map = TobogganMap::new(input, lines)?;
+    map.product(&SLOPES)
+}
 
-    product *= map.simulate(1, 1);
-    product *= map.simulate(3, 1);
-    product *= map.simulate(5, 1);
-    product *= map.simulate(7, 1);
-    product *= map.simulate(1, 2);
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Slope {
+    right: usize,
+    down: usize,
+}
 
-    Ok(product)
+impl Slope {
+    pub fn new(right: usize, down: usize) -> anyhow::Result<Self> {
+        if down == 0 {
+            return Err(anyhow!("A slope has to go down by at least 1"));
+        }
+
+        Ok(Slope { right, down })
+    }
 }
 
-struct Map(Vec<u32>, usize);
+// e.g. 3,1 for right 3, down 1
+impl FromStr for Slope {
+    type Err = anyhow::Error;
 
-impl Map {
-    fn new(lines: &[&str]) -> Map {
-        let length = lines[0].len();
-        let lines = lines.iter().map(|&line| Map::parse(line)).collect();
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let (right, down) = parse::split_once(string, ",")?;
+        Slope::new(parse::value(right)?, parse::value(down)?)
+    }
+}
 
-        Map(lines, length)
+impl std::fmt::Display for Slope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "right {}, down {}", self.right, self.down)
     }
+}
 
-    fn simulate(&self, dx: usize, dy: usize) -> usize {
-        let (mut x, mut y) = (0, 0);
-        let mut count = 0;
+// Every row is a bitset of its trees, 64 columns to a cell of the grid, so that rows can be of any
+// width
+pub struct TobogganMap {
+    width: usize,
+    trees: Grid<u64>,
+}
 
-        loop {
-            match self.is_tree(x, y) {
-                Some(true) => count += 1,
-                None => break count,
-                _ => {}
+impl TobogganMap {
+    pub fn new(input: &[u8], lines: &LineIndex) -> anyhow::Result<Self> {
+        let height = lines.len();
+        let width = lines
+            .first()
+            .ok_or_else(|| anyhow!("Cannot parse a map without any rows"))?
+            .len();
+
+        if width == 0 {
+            return Err(anyhow!("Cannot parse a map without any columns"));
+        }
+
+        let words_per_row = width.div_ceil(u64::BITS as usize);
+        let mut words = Vec::with_capacity(words_per_row * height);
+
+        for (y, row) in lines.lines(input).enumerate() {
+            if row.len() != width {
+                return Err(anyhow!(
+                    "Row {} has {} columns, expected {}",
+                    y + 1,
+                    row.len(),
+                    width
+                ));
             }
 
-            x += dx;
-            y += dy;
+            words.extend(row.chunks(u64::BITS as usize).map(|chunk| {
+                chunk
+                    .iter()
+                    .rev()
+                    .fold(0, |word, &char| word << 1 | (char == b'#') as u64)
+            }));
         }
+
+        Ok(TobogganMap {
+            width,
+            trees: Grid::new(words_per_row, height, words),
+        })
     }
 
-    fn is_tree(&self, x: usize, y: usize) -> Option<bool> {
-        if y >= self.0.len() {
-            None
-        } else {
-            Some((self.0[y] >> (x % self.1)) & 1 == 1)
+    // The column wraps around with a subtraction rather than a division on every row
+    pub fn trees(&self, slope: Slope) -> usize {
+        let right = slope.right % self.width;
+        let mut x = 0;
+        let mut count = 0;
+
+        for row in self.trees.rows().step_by(slope.down) {
+            count += (row[x / 64] >> (x % 64) & 1) as usize;
+            x += right;
+
+            if x >= self.width {
+                x -= self.width;
+            }
         }
+
+        count
     }
 
-    fn parse(line: &str) -> u32 {
-        line.as_bytes().iter().rev().fold(0u32, Map::build_line)
+    pub fn product(&self, slopes: &[Slope]) -> anyhow::Result<usize> {
+        slopes
+            .iter()
+            .try_fold(1usize, |product, &slope| {
+                product.checked_mul(self.trees(slope))
+            })
+            .ok_or_else(|| anyhow!("The product of the trees is too large"))
     }
 
-    fn build_line(acc: u32, char: &u8) -> u32 {
-        if *char == b'#' {
-            acc << 1 | 1
-        } else {
-            acc << 1
+    // The trees along every slope going at most bound to the right and down
+    pub fn search(&self, bound: usize) -> Vec<(Slope, usize)> {
+        (1..=bound)
+            .flat_map(|down| (0..=bound).map(move |right| Slope { right, down }))
+            .map(|slope| (slope, self.trees(slope)))
+            .collect()
+    }
+
+    // The map repeated as often as the path needs, with O where the path hits a tree and X where
+    // it does not
+    pub fn render(&self, slope: Slope) -> String {
+        let last = self.path(slope).last().map_or(0, |(x, _)| x);
+        let width = (last / self.width + 1) * self.width;
+
+        let mut rows = (0..self.trees.height())
+            .map(|y| {
+                (0..width)
+                    .map(|x| if self.is_tree(x, y) { b'#' } else { b'.' })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        for (x, y) in self.path(slope) {
+            rows[y][x] = if self.is_tree(x, y) { b'O' } else { b'X' };
         }
+
+        rows.iter()
+            .map(|row| String::from_utf8_lossy(row) + "\n")
+            .collect()
+    }
+
+    fn path(&self, slope: Slope) -> impl Iterator<Item = (usize, usize)> {
+        (0..self.trees.height())
+            .step_by(slope.down)
+            .enumerate()
+            .map(move |(step, y)| (step * slope.right, y))
+    }
+
+    // The map repeats to the right
+    fn is_tree(&self, x: usize, y: usize) -> bool {
+        let x = x % self.width;
+        self.trees[(x / 64, y)] >> (x % 64) & 1 == 1
+    }
+}
+
+impl FromStr for TobogganMap {
+    type Err = anyhow::Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.as_bytes();
+        TobogganMap::new(input, &LineIndex::new(input))
     }
 }
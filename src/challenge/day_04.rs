@@ -1,84 +1,449 @@
-const BYR_OFFSET: usize = 0;
-const IYR_OFFSET: usize = 1;
-const EYR_OFFSET: usize = 2;
-const HGT_OFFSET: usize = 3;
-const HCL_OFFSET: usize = 4;
-const ECL_OFFSET: usize = 5;
-const PID_OFFSET: usize = 6;
+use super::{parse, Part};
+use anyhow::{anyhow, Context};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Write};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
 
-const FIELD_NAMES: [&str; 7] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
-const FIELD_MASK: u8 = 0b1111111;
+lazy_static! {
+    static ref PART_A: PassportSchema = include_str!("../../schemas/passports_a.schema")
+        .parse()
+        .unwrap();
+    static ref PART_B: PassportSchema = include_str!("../../schemas/passports_b.schema")
+        .parse()
+        .unwrap();
+}
 
 pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    Ok(count_valid_passports(input, false))
+    Ok(PassportSchema::builtin(Part::PartA).valid_passports(input))
 }
 
 pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    Ok(count_valid_passports(input, true))
+    Ok(PassportSchema::builtin(Part::PartB).valid_passports(input))
 }
 
-fn count_valid_passports(input: &[&str], strict: bool) -> usize {
-    input
-        .split(|line| line.is_empty())
-        .filter(|&chunk| validate(chunk, strict))
-        .count()
+// The fields a passport can have and the rules for their values, one field per line as in
+// `hgt required units 150-193cm 59-76in`
+pub struct PassportSchema {
+    fields: Vec<Field>,
 }
 
-fn validate(lines: &[&str], strict: bool) -> bool {
-    let mut fields = 0u8;
+impl PassportSchema {
+    pub fn builtin(part: Part) -> &'static Self {
+        match part {
+            Part::PartA => &PART_A,
+            Part::PartB => &PART_B,
+        }
+    }
 
-    for field in lines.iter().flat_map(|line| line.split_whitespace()) {
-        if let Some(offset) = FIELD_NAMES.iter().position(|&x| x == &field[0..3]) {
-            fields |= 1 << offset;
+    pub fn count_valid(&self, input: &str) -> usize {
+        self.valid_passports(&input.lines().collect::<Vec<_>>())
+    }
 
-            let valid = match offset {
-                _ if !strict => true,
-                BYR_OFFSET => validate_year(&field[4..], 1920, 2002),
-                IYR_OFFSET => validate_year(&field[4..], 2010, 2020),
-                EYR_OFFSET => validate_year(&field[4..], 2020, 2030),
-                HGT_OFFSET => validate_height(&field[4..]),
-                HCL_OFFSET => validate_color(&field[4..]),
-                ECL_OFFSET => validate_eye_color(&field[4..]),
-                PID_OFFSET => validate_passport_id(&field[4..]),
-                _ => false,
-            };
+    // Why each passport is valid or not, along with how many passports each reason applies to
+    pub fn report(&self, input: &str, format: ReportFormat) -> String {
+        let lines = input.lines().collect::<Vec<_>>();
+        let verdicts = parse::sections(&lines)
+            .map(|passport| self.verdict(passport))
+            .collect::<Vec<_>>();
 
-            if !valid {
-                return false;
+        let mut reasons = BTreeMap::<String, usize>::new();
+
+        for verdict in &verdicts {
+            for reason in verdict.reasons() {
+                *reasons.entry(reason).or_default() += 1;
             }
         }
+
+        let mut reasons = reasons.into_iter().collect::<Vec<_>>();
+        reasons.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let valid = verdicts.iter().filter(|verdict| verdict.is_valid()).count();
+
+        match format {
+            ReportFormat::Text => text_report(&verdicts, valid, &reasons),
+            ReportFormat::Json => json_report(&verdicts, valid, &reasons),
+        }
+    }
+
+    fn valid_passports(&self, input: &[&str]) -> usize {
+        parse::sections(input)
+            .filter(|&passport| self.verdict(passport).is_valid())
+            .count()
     }
 
-    fields == FIELD_MASK
+    fn verdict<'a>(&'a self, passport: parse::Section<'_, &'a str>) -> Verdict<'a> {
+        let mut verdict = Verdict {
+            line: passport.line_number(0),
+            missing: Vec::new(),
+            invalid: Vec::new(),
+            unknown: Vec::new(),
+        };
+        let mut present = vec![false; self.fields.len()];
+
+        for entry in passport
+            .lines
+            .iter()
+            .flat_map(|line| line.split_whitespace())
+        {
+            let (name, value) = entry.split_once(':').unwrap_or((entry, ""));
+
+            match self.fields.iter().position(|field| field.name == name) {
+                Some(i) => {
+                    if !self.fields[i].rule.check(value) {
+                        verdict.invalid.push((&self.fields[i], value));
+                    }
+
+                    present[i] = true;
+                }
+                None => verdict.unknown.push(name),
+            }
+        }
+
+        verdict.missing = self
+            .fields
+            .iter()
+            .zip(present)
+            .filter(|(field, present)| field.required && !present)
+            .map(|(field, _)| field.name.as_str())
+            .collect();
+
+        verdict
+    }
 }
 
-fn validate_year(value: &str, min: u32, max: u32) -> bool {
-    matches!(value.parse::<u32>(), Ok(year) if year >= min && year <= max)
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ReportFormat {
+    Text,
+    Json,
 }
 
-fn validate_height(value: &str) -> bool {
-    let index = value.len() - 2;
-    let height = value[..index].parse::<u8>().unwrap_or(0);
+// Fields which are not in the schema are reported, but do not make the passport invalid
+struct Verdict<'a> {
+    line: usize,
+    missing: Vec<&'a str>,
+    invalid: Vec<(&'a Field, &'a str)>,
+    unknown: Vec<&'a str>,
+}
+
+impl Verdict<'_> {
+    fn is_valid(&self) -> bool {
+        self.missing.is_empty() && self.invalid.is_empty()
+    }
 
-    match &value[index..] {
-        "cm" => (150..=193).contains(&height),
-        "in" => (59..=76).contains(&height),
-        _ => false,
+    // Each reason once, even when a field is there more than once
+    fn reasons(&self) -> BTreeSet<String> {
+        let missing = self.missing.iter().map(|name| format!("missing {}", name));
+        let invalid = self
+            .invalid
+            .iter()
+            .map(|(field, _)| format!("invalid {}", field.name));
+        let unknown = self.unknown.iter().map(|name| format!("unknown {}", name));
+
+        missing.chain(invalid).chain(unknown).collect()
     }
 }
 
-fn validate_color(value: &str) -> bool {
-    if value.len() != 7 || value.as_bytes()[0] != b'#' {
-        return false;
+fn text_report(verdicts: &[Verdict], valid: usize, reasons: &[(String, usize)]) -> String {
+    let mut report = String::new();
+
+    for verdict in verdicts {
+        let mut details = vec![if verdict.is_valid() {
+            "valid"
+        } else {
+            "invalid"
+        }
+        .to_string()];
+
+        if !verdict.missing.is_empty() {
+            details.push(format!("missing {}", verdict.missing.join(", ")));
+        }
+
+        details.extend(
+            verdict
+                .invalid
+                .iter()
+                .map(|(field, value)| format!("{}:{} violates {}", field.name, value, field.rule)),
+        );
+
+        if !verdict.unknown.is_empty() {
+            details.push(format!("unknown {}", verdict.unknown.join(", ")));
+        }
+
+        writeln!(report, "Line {}: {}", verdict.line, details.join("; ")).unwrap();
     }
 
-    value[1..].chars().all(|x| x.is_ascii_hexdigit())
+    writeln!(
+        report,
+        "{} of {} passports are valid",
+        valid,
+        verdicts.len()
+    )
+    .unwrap();
+
+    if !reasons.is_empty() {
+        writeln!(report, "Passports per reason:").unwrap();
+
+        for (reason, count) in reasons {
+            writeln!(report, "  {} {}", count, reason).unwrap();
+        }
+    }
+
+    report
+}
+
+fn json_report(verdicts: &[Verdict], valid: usize, reasons: &[(String, usize)]) -> String {
+    let strings = |strings: &[&str]| {
+        let strings = strings.iter().map(|x| json_string(x)).collect::<Vec<_>>();
+        format!("[{}]", strings.join(", "))
+    };
+
+    let passports = verdicts
+        .iter()
+        .map(|verdict| {
+            let invalid = verdict
+                .invalid
+                .iter()
+                .map(|(field, value)| {
+                    format!(
+                        "{{\"field\": {}, \"value\": {}, \"rule\": {}}}",
+                        json_string(&field.name),
+                        json_string(value),
+                        json_string(&field.rule.to_string())
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            format!(
+                "    {{\"line\": {}, \"valid\": {}, \"missing\": {}, \"invalid\": [{}], \
+                 \"unknown\": {}}}",
+                verdict.line,
+                verdict.is_valid(),
+                strings(&verdict.missing),
+                invalid.join(", "),
+                strings(&verdict.unknown)
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let reasons = reasons
+        .iter()
+        .map(|(reason, count)| {
+            format!(
+                "    {{\"reason\": {}, \"passports\": {}}}",
+                json_string(reason),
+                count
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let list = |items: Vec<String>| {
+        if items.is_empty() {
+            "[]".to_string()
+        } else {
+            format!("[\n{}\n  ]", items.join(",\n"))
+        }
+    };
+
+    format!(
+        "{{\n  \"passports\": {},\n  \"valid\": {},\n  \"total\": {},\n  \"reasons\": {}\n}}\n",
+        list(passports),
+        valid,
+        verdicts.len(),
+        list(reasons)
+    )
 }
 
-fn validate_eye_color(value: &str) -> bool {
-    matches!(value, "amb" | "blu" | "brn" | "gry" | "grn" | "hzl" | "oth")
+fn json_string(string: &str) -> String {
+    let mut json = String::from('"');
+
+    for char in string.chars() {
+        match char {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            char if char.is_control() => write!(json, "\\u{:04x}", char as u32).unwrap(),
+            char => json.push(char),
+        }
+    }
+
+    json.push('"');
+    json
 }
 
-fn validate_passport_id(value: &str) -> bool {
-    value.len() == 9 && value.chars().all(|x| x.is_ascii_digit())
+impl FromStr for PassportSchema {
+    type Err = anyhow::Error;
+
+    fn from_str(schema: &str) -> Result<Self, Self::Err> {
+        let lines = schema.lines().collect::<Vec<_>>();
+
+        // Blank lines and comments starting with # are skipped
+        let fields = parse::lines_with(&lines, |line| match line.trim() {
+            line if line.is_empty() || line.starts_with('#') => Ok(None),
+            line => line.parse().map(Some),
+        })?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<Field>>();
+
+        for (i, field) in fields.iter().enumerate() {
+            if fields[..i].iter().any(|other| other.name == field.name) {
+                return Err(anyhow!("Field {} is defined more than once", field.name));
+            }
+        }
+
+        if fields.is_empty() {
+            return Err(anyhow!("The schema has no fields"));
+        }
+
+        Ok(PassportSchema { fields })
+    }
+}
+
+struct Field {
+    name: String,
+    required: bool,
+    rule: Rule,
+}
+
+impl FromStr for Field {
+    type Err = anyhow::Error;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (name, line) = split_word(line);
+        let (presence, rule) = split_word(line);
+
+        let required = match presence {
+            "required" => true,
+            "optional" => false,
+            _ => {
+                return Err(anyhow!(
+                    "Expected required or optional after {}, found {:?}",
+                    name,
+                    presence
+                ))
+            }
+        };
+
+        let rule = rule
+            .parse()
+            .with_context(|| format!("Invalid rule for {}", name))?;
+
+        Ok(Field {
+            name: name.to_string(),
+            required,
+            rule,
+        })
+    }
+}
+
+enum Rule {
+    Any,
+    // Exactly four digits
+    Year(RangeInclusive<u32>),
+    // A number with one of the units right after it, e.g. `183cm`
+    Units(Vec<(String, RangeInclusive<u32>)>),
+    // Has to match the whole value, kept along with the pattern as written in the schema
+    Pattern(String, Regex),
+    OneOf(Vec<String>),
+}
+
+impl Rule {
+    fn check(&self, value: &str) -> bool {
+        match self {
+            Rule::Any => true,
+            Rule::Year(range) => value.len() == 4 && in_range(value, range),
+            Rule::Units(units) => units.iter().any(|(unit, range)| {
+                matches!(value.strip_suffix(unit.as_str()), Some(number) if in_range(number, range))
+            }),
+            Rule::Pattern(_, regex) => regex.is_match(value),
+            Rule::OneOf(values) => values.iter().any(|x| x == value),
+        }
+    }
+}
+
+// As written in the schema
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let range = |range: &RangeInclusive<u32>| format!("{}-{}", range.start(), range.end());
+
+        match self {
+            Rule::Any => write!(f, "any value"),
+            Rule::Year(years) => write!(f, "year {}", range(years)),
+            Rule::Units(units) => {
+                let units = units
+                    .iter()
+                    .map(|(unit, values)| format!("{}{}", range(values), unit))
+                    .collect::<Vec<_>>();
+
+                write!(f, "units {}", units.join(" "))
+            }
+            Rule::Pattern(pattern, _) => write!(f, "regex {}", pattern),
+            Rule::OneOf(values) => write!(f, "one-of {}", values.join(" ")),
+        }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = anyhow::Error;
+
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        let (kind, arguments) = split_word(rule);
+
+        if !kind.is_empty() && arguments.is_empty() {
+            return Err(anyhow!("Missing arguments for {}", kind));
+        }
+
+        match kind {
+            "" => Ok(Rule::Any),
+            "year" => Ok(Rule::Year(range(arguments)?)),
+            "units" => arguments
+                .split_whitespace()
+                .map(|unit| {
+                    let name = unit.trim_start_matches(|x: char| x.is_ascii_digit() || x == '-');
+
+                    if name.is_empty() {
+                        return Err(anyhow!("Missing unit after {}", unit));
+                    }
+
+                    Ok((name.to_string(), range(&unit[..unit.len() - name.len()])?))
+                })
+                .collect::<anyhow::Result<_>>()
+                .map(Rule::Units),
+            "regex" => Ok(Rule::Pattern(
+                arguments.to_string(),
+                Regex::new(&format!("^(?:{})$", arguments))?,
+            )),
+            "one-of" => Ok(Rule::OneOf(
+                arguments.split_whitespace().map(str::to_string).collect(),
+            )),
+            _ => Err(anyhow!(
+                "Unknown rule {}, expecting year, units, regex or one-of",
+                kind
+            )),
+        }
+    }
+}
+
+// e.g. `150-193`
+fn range(range: &str) -> anyhow::Result<RangeInclusive<u32>> {
+    let (min, max) = parse::split_once(range, "-")?;
+    Ok(parse::value(min)?..=parse::value(max)?)
+}
+
+// Only digits, unlike parsing which also takes a sign
+fn in_range(number: &str, range: &RangeInclusive<u32>) -> bool {
+    !number.is_empty()
+        && number.bytes().all(|x| x.is_ascii_digit())
+        && matches!(number.parse(), Ok(number) if range.contains(&number))
+}
+
+fn split_word(string: &str) -> (&str, &str) {
+    let (word, rest) = string
+        .trim()
+        .split_once(char::is_whitespace)
+        .unwrap_or((string.trim(), ""));
+
+    (word, rest.trim())
 }
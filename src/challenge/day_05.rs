@@ -1,47 +1,371 @@
-use anyhow::Context;
-use std::ops::Add;
+use super::LineIndex;
+use anyhow::{anyhow, Context};
+use std::collections::BTreeMap;
+use std::fmt::Write;
 
-pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    Ok(input.iter().map(|line| Seat::new(line)).max().unwrap().id())
+// Larger planes only show the rows with passes in their seat map, and wider ones have no map at all
+const MAX_MAP_ROWS: u32 = 1024;
+const MAX_MAP_COLUMNS: u32 = 256;
+
+pub fn part_a(input: &[u8], lines: &LineIndex) -> anyhow::Result<impl std::fmt::Display> {
+    let seat = decode_all(input, lines)?.into_iter().max();
+    Ok(seat.context("Cannot find any boarding passes")?.id())
 }
 
-pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let mut seats = input.iter().map(|line| Seat::new(line)).collect::<Vec<_>>();
+pub fn part_b(input: &[u8], lines: &LineIndex) -> anyhow::Result<impl std::fmt::Display> {
+    let mut seats = decode_all(input, lines)?;
     seats.sort();
-    let missing_seat = find_missing_seat(seats).context("Cannot find missing seat")?;
-    Ok(missing_seat.id())
+    find_missing_seat(seats).context("Cannot find missing seat")
+}
+
+// Passes with any other character or of another length are an error, rather than read as some
+// other seat
+fn decode_all(input: &[u8], lines: &LineIndex) -> anyhow::Result<Vec<Seat>> {
+    let plane = Plane::default();
+
+    lines
+        .lines(input)
+        .enumerate()
+        .map(|(i, line)| {
+            plane
+                .decode_bytes(line)
+                .with_context(|| format!("Invalid boarding pass on line {}", i + 1))
+        })
+        .collect()
+}
+
+// A boarding pass is the bits of the row, F for 0 and B for 1, followed by the bits of the
+// column, L for 0 and R for 1. The seat ID puts the column bits after the row bits as well.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct Plane {
+    row_bits: u32,
+    column_bits: u32,
+}
+
+impl Default for Plane {
+    fn default() -> Self {
+        Plane {
+            row_bits: 7,
+            column_bits: 3,
+        }
+    }
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
-struct Seat(u16);
+impl Plane {
+    pub fn new(row_bits: u32, column_bits: u32) -> anyhow::Result<Self> {
+        if row_bits == 0 || column_bits == 0 {
+            return Err(anyhow!(
+                "A plane needs at least one row bit and one column bit"
+            ));
+        }
+
+        if row_bits + column_bits > u32::BITS {
+            return Err(anyhow!(
+                "Seat IDs have at most {} bits, found {} row bits and {} column bits",
+                u32::BITS,
+                row_bits,
+                column_bits
+            ));
+        }
+
+        Ok(Plane {
+            row_bits,
+            column_bits,
+        })
+    }
+
+    pub fn rows(&self) -> u32 {
+        1 << self.row_bits
+    }
+
+    pub fn columns(&self) -> u32 {
+        1 << self.column_bits
+    }
+
+    pub fn decode(&self, pass: &str) -> anyhow::Result<Seat> {
+        self.decode_bytes(pass.as_bytes())
+    }
+
+    pub fn seat(&self, row: u32, column: u32) -> anyhow::Result<Seat> {
+        if row >= self.rows() {
+            return Err(anyhow!("Row {} is outside of {} rows", row, self.rows()));
+        }
+
+        if column >= self.columns() {
+            return Err(anyhow!(
+                "Column {} is outside of {} columns",
+                column,
+                self.columns()
+            ));
+        }
+
+        Ok(Seat {
+            id: row << self.column_bits | column,
+            row,
+            column,
+            plane: *self,
+        })
+    }
+
+    pub fn seat_with_id(&self, id: u32) -> anyhow::Result<Seat> {
+        let seats = u64::from(self.rows()) * u64::from(self.columns());
+
+        if u64::from(id) >= seats {
+            return Err(anyhow!("Seat ID {} is outside of {} seats", id, seats));
+        }
+
+        self.seat(id >> self.column_bits, id & (self.columns() - 1))
+    }
+
+    // A map of the plane with the occupancy of every row, followed by every empty seat and every
+    // seat with more than one boarding pass. Apart from the map, the work only depends on the
+    // number of passes, as planes can have billions of seats.
+    pub fn analyze(&self, input: &str) -> anyhow::Result<String> {
+        let mut passes = BTreeMap::<u32, Vec<usize>>::new();
+
+        for (i, line) in input.lines().enumerate() {
+            let seat = self
+                .decode(line)
+                .with_context(|| format!("Invalid boarding pass on line {}", i + 1))?;
+            passes.entry(seat.id).or_default().push(i + 1);
+        }
+
+        let mut report = String::new();
+        let width = (self.rows() - 1).to_string().len();
+
+        let rows = if self.columns() > MAX_MAP_COLUMNS {
+            writeln!(
+                report,
+                "No seat map, rows of {} seats are too wide to show",
+                self.columns()
+            )?;
+            Vec::new()
+        } else if self.rows() > MAX_MAP_ROWS {
+            writeln!(
+                report,
+                "Seat map of the rows with passes, # for a taken seat, 2-9 for a seat on that many \
+                 passes, . for an empty seat"
+            )?;
+            let mut rows = passes
+                .keys()
+                .map(|id| id >> self.column_bits)
+                .collect::<Vec<_>>();
+            rows.dedup();
+            rows
+        } else {
+            writeln!(
+                report,
+                "Seat map, # for a taken seat, 2-9 for a seat on that many passes, . for an empty \
+                 seat"
+            )?;
+            (0..self.rows()).collect()
+        };
+
+        for row in rows {
+            let seats = (0..self.columns())
+                .map(|column| {
+                    passes
+                        .get(&(row << self.column_bits | column))
+                        .map(Vec::len)
+                })
+                .collect::<Vec<_>>();
+
+            let map = seats
+                .iter()
+                .map(|count| match count {
+                    None => '.',
+                    Some(1) => '#',
+                    Some(count) => char::from_digit(*count as u32, 10).unwrap_or('9'),
+                })
+                .collect::<String>();
+
+            let taken = seats.iter().flatten().count();
+            writeln!(
+                report,
+                "{:>width$} {} {}/{}",
+                row,
+                map,
+                taken,
+                self.columns(),
+                width = width
+            )?;
+        }
+
+        // The empty seats are the gaps between the taken ones, a gap of one seat after a taken
+        // seat lies between two taken ones
+        let seats = u64::from(self.rows()) * u64::from(self.columns());
+        let mut empty = Vec::<(u64, u64)>::new();
+        let mut between = Vec::new();
+        let mut next = 0;
+
+        for id in passes.keys().map(|&id| u64::from(id)) {
+            if id > next {
+                empty.push((next, id - 1));
+
+                if next > 0 && id == next + 1 {
+                    between.push(self.seat_with_id(next as u32)?);
+                }
+            }
+
+            next = id + 1;
+        }
+
+        if next < seats {
+            empty.push((next, seats - 1));
+        }
+
+        writeln!(report, "\n{} of {} seats taken", passes.len(), seats)?;
+
+        let empty = empty
+            .iter()
+            .map(|&(start, end)| {
+                if start == end {
+                    start.to_string()
+                } else {
+                    format!("{}-{}", start, end)
+                }
+            })
+            .collect::<Vec<_>>();
+        writeln!(report, "Empty seat IDs: {}", list(&empty))?;
+
+        let between = between
+            .iter()
+            .map(|seat| format!("{} ({})", seat.pass(), seat))
+            .collect::<Vec<_>>();
+        writeln!(
+            report,
+            "Empty seats between two taken ones: {}",
+            list(&between)
+        )?;
+
+        let duplicates = passes
+            .iter()
+            .filter(|(_, lines)| lines.len() > 1)
+            .map(|(&id, lines)| {
+                let seat = self.seat_with_id(id)?;
+                let lines = lines.iter().map(usize::to_string).collect::<Vec<_>>();
+                Ok(format!(
+                    "{} ({}) on lines {}",
+                    seat.pass(),
+                    seat,
+                    lines.join(", ")
+                ))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        writeln!(report, "Duplicate passes: {}", list(&duplicates))?;
+
+        Ok(report)
+    }
+
+    fn decode_bytes(&self, pass: &[u8]) -> anyhow::Result<Seat> {
+        let length = (self.row_bits + self.column_bits) as usize;
+
+        if pass.len() != length {
+            return Err(anyhow!(
+                "Expected {} characters, found {}",
+                length,
+                pass.len()
+            ));
+        }
+
+        let mut id = 0u32;
+
+        for (i, &char) in pass.iter().enumerate() {
+            let (zero, one) = if i < self.row_bits as usize {
+                (b'F', b'B')
+            } else {
+                (b'L', b'R')
+            };
+
+            let bit = match char {
+                _ if char == zero => 0,
+                _ if char == one => 1,
+                _ => {
+                    return Err(anyhow!(
+                        "Invalid character {:?} at position {}, expecting {} or {}",
+                        char as char,
+                        i + 1,
+                        zero as char,
+                        one as char
+                    ))
+                }
+            };
+
+            id = id << 1 | bit;
+        }
+
+        self.seat_with_id(id)
+    }
+}
+
+// Seats are ordered by their IDs
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct Seat {
+    id: u32,
+    row: u32,
+    column: u32,
+    plane: Plane,
+}
 
 impl Seat {
-    fn new(string: &str) -> Self {
-        Seat(string.as_bytes().iter().fold(0u16, |acc, char| {
-            acc << 1 | (matches!(char, b'B' | b'R') as u16)
-        }))
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn row(&self) -> u32 {
+        self.row
     }
 
-    fn id(&self) -> u16 {
-        self.0
+    pub fn column(&self) -> u32 {
+        self.column
+    }
+
+    // Encodes the seat back into its boarding pass
+    pub fn pass(&self) -> String {
+        let bits = self.plane.row_bits + self.plane.column_bits;
+
+        (0..bits)
+            .rev()
+            .map(|bit| {
+                let set = self.id >> bit & 1 == 1;
+
+                match (bit >= self.plane.column_bits, set) {
+                    (true, false) => 'F',
+                    (true, true) => 'B',
+                    (false, false) => 'L',
+                    (false, true) => 'R',
+                }
+            })
+            .collect()
     }
 }
 
-impl Add<u16> for Seat {
-    type Output = Seat;
+impl std::fmt::Display for Seat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "row {}, column {}, seat ID {}",
+            self.row, self.column, self.id
+        )
+    }
+}
 
-    fn add(self, rhs: u16) -> Self::Output {
-        Seat(self.0 + rhs)
+fn list(items: &[String]) -> String {
+    if items.is_empty() {
+        "none".to_string()
+    } else {
+        items.join(", ")
     }
 }
 
-fn find_missing_seat(seats: impl IntoIterator<Item = Seat>) -> Option<Seat> {
+// The ID between two seats whose IDs are two apart
+fn find_missing_seat(seats: impl IntoIterator<Item = Seat>) -> Option<u32> {
     let mut iterator = seats.into_iter();
-    let mut previous: Seat = iterator.next()?;
+    let mut previous = iterator.next()?;
 
     for next in iterator {
-        if previous + 2 == next {
-            return Some(previous + 1);
+        if next.id - previous.id == 2 {
+            return Some(previous.id + 1);
         }
 
         previous = next;
@@ -1,37 +1,55 @@
+use super::{parse, LineIndex};
+use anyhow::anyhow;
 use std::ops::{BitAnd, BitOr};
 
-pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    Ok(sum_answer_counts(input, Answers::bitor))
+pub fn part_a(input: &[u8], lines: &LineIndex) -> anyhow::Result<impl std::fmt::Display> {
+    sum_answer_counts(input, lines, Answers::bitor)
 }
 
-pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    Ok(sum_answer_counts(input, Answers::bitand))
+pub fn part_b(input: &[u8], lines: &LineIndex) -> anyhow::Result<impl std::fmt::Display> {
+    sum_answer_counts(input, lines, Answers::bitand)
 }
 
-fn sum_answer_counts(input: &[&str], f: impl Fn(Answers, Answers) -> Answers + Copy) -> usize {
-    input
-        .split(|line| line.is_empty())
-        .map(|lines| {
-            lines
-                .iter()
-                .map(|line| Answers::new(line))
-                .reduce(f)
-                .unwrap_or_default()
-                .count()
-        })
-        .sum::<usize>()
+fn sum_answer_counts(
+    input: &[u8],
+    lines: &LineIndex,
+    f: impl Fn(Answers, Answers) -> Answers + Copy,
+) -> anyhow::Result<usize> {
+    let mut sum = 0;
+
+    for group in parse::sections(lines) {
+        let mut answers = None;
+
+        for line in group.lines {
+            let person = Answers::new(&input[line.clone()])?;
+            answers = Some(match answers {
+                Some(answers) => f(answers, person),
+                None => person,
+            });
+        }
+
+        sum += answers.unwrap_or_default().count();
+    }
+
+    Ok(sum)
 }
 
 #[derive(Default)]
 struct Answers(u32);
 
 impl Answers {
-    fn new(line: &str) -> Answers {
-        let value = line
-            .bytes()
-            .fold(0u32, |acc, byte| acc | 1 << (byte - b'a') as usize);
+    fn new(line: &[u8]) -> anyhow::Result<Answers> {
+        let mut value = 0u32;
+
+        for byte in line {
+            if !byte.is_ascii_lowercase() {
+                return Err(anyhow!("Unexpected answer {:?}", *byte as char));
+            }
+
+            value |= 1 << (byte - b'a');
+        }
 
-        Answers(value)
+        Ok(Answers(value))
     }
 
     fn count(&self) -> usize {
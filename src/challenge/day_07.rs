@@ -1,4 +1,4 @@
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 use std::collections::{HashMap, HashSet};
 
 pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
@@ -39,7 +39,7 @@ pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
         }
     }
 
-    Ok(count_children("shiny gold", &mut graph))
+    count_children("shiny gold", &mut graph, 0)
 }
 
 #[derive(Default)]
@@ -97,26 +97,39 @@ fn visit_children<'a>(
     }
 }
 
-fn count_children(parent: &str, graph: &mut HashMap<&str, Entry>) -> usize {
+fn count_children(
+    parent: &str,
+    graph: &mut HashMap<&str, Entry>,
+    depth: usize,
+) -> anyhow::Result<usize> {
     let entry = match graph.get(parent) {
         Some(entry) => entry,
-        None => return 0,
+        None => return Ok(0),
     };
 
     if let Some(total_children) = entry.total_children {
-        return total_children;
+        return Ok(total_children);
+    }
+
+    // Any deeper nesting has to contain one of the bags twice
+    if depth > graph.len() {
+        return Err(anyhow!("{} bags end up containing themselves", parent));
     }
 
-    let total_children = entry
-        .children
-        .clone()
-        .iter()
-        .map(|child| child.count * (1 + count_children(child.name, graph)))
-        .sum();
+    let mut total_children = 0usize;
+
+    for child in entry.children.clone() {
+        let count = count_children(child.name, graph, depth + 1)?
+            .checked_add(1)
+            .and_then(|count| count.checked_mul(child.count))
+            .and_then(|count| count.checked_add(total_children));
+
+        total_children = count.context("Too many bags to count")?;
+    }
 
     if let Some(entry) = graph.get_mut(parent) {
         entry.total_children = Some(total_children);
     }
 
-    total_children
+    Ok(total_children)
 }
@@ -1,4 +1,5 @@
-use anyhow::anyhow;
+use super::parse;
+use anyhow::{anyhow, Context};
 use std::str::FromStr;
 
 pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
@@ -7,11 +8,17 @@ pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
     let mut program = Program::new(&instructions);
     let mut visited = BitSet::new(instructions.len());
 
-    while visited.set(program.pos()) {
+    while program.pos() < instructions.len() && visited.set(program.pos()) {
         program.execute();
     }
 
-    Ok(program.acc())
+    if program.pos() < instructions.len() {
+        Ok(program.acc())
+    } else {
+        Err(anyhow!(
+            "Program terminates without repeating an instruction"
+        ))
+    }
 }
 
 pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
@@ -23,9 +30,16 @@ pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
     resolve_destinations(&mut destinations);
 
     let mut program = Program::new(&instructions);
+    let mut visited = BitSet::new(instructions.len());
 
     loop {
-        let instruction = &instructions[program.pos()];
+        let instruction = instructions
+            .get(program.pos())
+            .context("Program terminates without a fix")?;
+
+        if !visited.set(program.pos()) {
+            return Err(anyhow!("Cannot find an instruction which fixes the loop"));
+        }
 
         if let Some(pos) = find_loop_exit_pos(program.pos(), instruction, &destinations) {
             program.set_pos(pos);
@@ -35,7 +49,13 @@ pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
         program.execute();
     }
 
+    let mut visited = BitSet::new(instructions.len());
+
     while program.pos() < instructions.len() {
+        if !visited.set(program.pos()) {
+            return Err(anyhow!("Program still loops after the fix"));
+        }
+
         program.execute()
     }
 
@@ -53,7 +73,11 @@ fn find_loop_exit_pos(
         _ => return None,
     };
 
-    if destinations[pos] == usize::MAX {
+    // Positions outside of the program exit it as well
+    if destinations
+        .get(pos)
+        .is_none_or(|destination| *destination == usize::MAX)
+    {
         Some(pos)
     } else {
         None
@@ -144,12 +168,11 @@ impl<'a> Program<'a> {
     }
 }
 
+// Jumping before the first instruction leaves the program just like jumping past the last one
 fn advance_pos(position: usize, amount: i16) -> usize {
-    if amount.is_negative() {
-        position - (-amount) as usize
-    } else {
-        position + amount as usize
-    }
+    position
+        .checked_add_signed(amount as isize)
+        .unwrap_or(usize::MAX)
 }
 
 #[derive(Copy, Clone)]
@@ -163,7 +186,7 @@ struct Instruction(Operation, i16);
 
 impl Instruction {
     fn parse(lines: &[&str]) -> anyhow::Result<Vec<Instruction>> {
-        lines.iter().map(|line| line.parse()).collect()
+        parse::lines(lines)
     }
 
     fn operation(&self) -> Operation {
@@ -179,14 +202,16 @@ impl FromStr for Instruction {
     type Err = anyhow::Error;
 
     fn from_str(string: &str) -> Result<Self, Self::Err> {
-        let operation = match &string[..3] {
+        let (operation, value) = parse::split_once(string, " ")?;
+
+        let operation = match operation {
             "acc" => Operation::Acc,
             "jmp" => Operation::Jmp,
             "nop" => Operation::Nop,
-            string => return Err(anyhow!("Unknown instruction {}", string)),
+            operation => return Err(anyhow!("Unknown instruction {}", operation)),
         };
 
-        Ok(Instruction(operation, string[4..].parse()?))
+        Ok(Instruction(operation, parse::value(value)?))
     }
 }
 
@@ -197,7 +222,7 @@ struct BitSet(Vec<usize>);
 
 impl BitSet {
     fn new(capacity: usize) -> Self {
-        BitSet(vec![0usize; (capacity - 1) / BUCKET_SIZE + 1])
+        BitSet(vec![0usize; capacity.div_ceil(BUCKET_SIZE)])
     }
 
     fn set(&mut self, bit: usize) -> bool {
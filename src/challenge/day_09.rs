@@ -1,71 +1,96 @@
-use anyhow::anyhow;
+use super::parse;
+use anyhow::Context;
 use std::cmp::Ordering;
+use std::fmt::Write;
+use std::ops::Range;
 
 const PREAMBLE_SIZE: usize = 25;
 
 pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let mut preamble = Preamble::new();
+    let numbers = parse::lines(input)?;
+    let invalid = find_invalid(&numbers).context("Could not find invalid number")?;
 
-    for line in &input[..PREAMBLE_SIZE] {
-        preamble.add(line.parse()?);
-    }
+    Ok(numbers[invalid])
+}
 
-    for line in &input[PREAMBLE_SIZE..] {
-        let value = line.parse()?;
+pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
+    let numbers = parse::lines(input)?;
+    let invalid = find_invalid(&numbers).context("Could not find invalid number")?;
 
-        if !preamble.add_checked(value) {
-            return Ok(value);
-        }
-    }
+    let range =
+        find_range(&numbers, numbers[invalid]).context("Could not find a range adding up to it")?;
+    let range = &numbers[range];
+    let min = range.iter().min().unwrap();
+    let max = range.iter().max().unwrap();
 
-    Err(anyhow!("Could not find invalid number"))
+    Ok(min + max)
 }
 
-pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let mut preamble = Preamble::new();
-    let mut numbers = Vec::with_capacity(input.len());
-
-    for line in &input[..PREAMBLE_SIZE] {
-        let value = line.parse()?;
-        numbers.push(value);
-        preamble.add(value);
+pub fn explain(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
+    let numbers = parse::lines(input)?;
+    let invalid = find_invalid(&numbers).context("Could not find invalid number")?;
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "Invalid number: {} on line {}",
+        numbers[invalid],
+        invalid + 1
+    )?;
+
+    match find_range(&numbers, numbers[invalid]) {
+        Some(range) => {
+            let lines = format!("{} to {}", range.start + 1, range.end);
+            let range = &numbers[range];
+            let min = range.iter().min().unwrap();
+            let max = range.iter().max().unwrap();
+
+            writeln!(
+                output,
+                "Range adding up to it: lines {}, {:?}",
+                lines, range
+            )?;
+            writeln!(output, "Smallest {} + largest {} = {}", min, max, min + max)?;
+        }
+        None => writeln!(output, "No range adds up to it")?,
     }
 
-    let mut invalid = 0u64;
+    Ok(output)
+}
 
-    for line in &input[PREAMBLE_SIZE..] {
-        let value = line.parse()?;
-        numbers.push(value);
+// Position of the first number which is not the sum of two of the numbers before it
+fn find_invalid(numbers: &[u64]) -> Option<usize> {
+    let mut preamble = Preamble::new();
 
-        if !preamble.add_checked(value) {
-            invalid = value;
-            break;
-        }
+    for value in numbers.iter().take(PREAMBLE_SIZE) {
+        preamble.add(*value);
     }
 
-    let range = find_range(&numbers, invalid);
-    let min = range.iter().min().unwrap();
-    let max = range.iter().max().unwrap();
-
-    Ok(min + max)
+    numbers
+        .iter()
+        .skip(PREAMBLE_SIZE)
+        .position(|value| !preamble.add_checked(*value))
+        .map(|position| position + PREAMBLE_SIZE)
 }
 
-fn find_range(numbers: &[u64], sum: u64) -> &[u64] {
+// Contiguous range of at least two numbers which adds up to the sum
+fn find_range(numbers: &[u64], sum: u64) -> Option<Range<usize>> {
+    let sum = sum as u128;
     let mut start = 0usize;
     let mut end = 0usize;
-    let mut acc = 0u64;
+    let mut acc = 0u128;
 
     loop {
         match acc.cmp(&sum) {
-            Ordering::Less => {
-                acc += numbers[end];
+            Ordering::Equal if end - start >= 2 => break Some(start..end),
+            Ordering::Less | Ordering::Equal => {
+                acc += *numbers.get(end)? as u128;
                 end += 1;
             }
             Ordering::Greater => {
-                acc -= numbers[start];
+                acc -= numbers[start] as u128;
                 start += 1;
             }
-            Ordering::Equal => break &numbers[start..end],
         }
     }
 }
@@ -1,77 +1,65 @@
-pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let mut odds = 0usize;
-    let mut max = 0usize;
+use super::parse;
+use anyhow::{anyhow, Context};
 
-    for line in input {
-        let value = line.parse::<usize>()?;
+const MAX_DIFFERENCE: usize = 3;
 
-        if value & 1 == 1 {
-            odds += 1;
-        }
+pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
+    let jolts = parse_jolts(input)?;
+    let mut differences = [0usize; MAX_DIFFERENCE + 1];
+    let mut previous = 0;
 
-        if value > max {
-            max = value;
-        }
-    }
+    for jolt in jolts {
+        let difference = jolt - previous;
 
-    let evens = input.len() - odds + 1;
+        *differences
+            .get_mut(difference)
+            .with_context(|| format!("No adapter can connect {} to {} jolts", previous, jolt))? +=
+            1;
 
-    let x = (max - input.len()) / 2;
-    // & !1 accounts for an additional difference of 1 caused by an odd number of inputs
-    let y = abs_diff(odds, evens) & !1;
-    let z = input.len() - x - y;
+        previous = jolt;
+    }
 
-    Ok((x + 1) * z)
+    // The device itself is always 3 jolts above the highest adapter
+    Ok(differences[1] * (differences[3] + 1))
 }
 
 pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let mut jolts = input
-        .iter()
-        .map(|line| line.parse())
-        .collect::<Result<Vec<usize>, _>>()?;
-
-    jolts.sort_unstable();
-
+    let jolts = parse_jolts(input)?;
     let mut paths = vec![0; jolts.len()];
 
-    paths[0] = 1;
-    paths[1] = compute_paths_initial(jolts[1], &jolts[..1], &paths[..1]);
-    paths[2] = compute_paths_initial(jolts[2], &jolts[..2], &paths[..2]);
+    for (j, &x) in jolts.iter().enumerate() {
+        let i = j.saturating_sub(MAX_DIFFERENCE);
+        let initial = (x <= MAX_DIFFERENCE) as usize;
 
-    for (i, &x) in jolts[3..].iter().enumerate() {
-        let j = i + 3;
-        paths[j] = compute_paths(x, &jolts[i..j], &paths[i..j]);
+        paths[j] = compute_paths(x, &jolts[i..j], &paths[i..j], initial)
+            .context("Too many arrangements to count")?;
     }
 
-    Ok(paths[jolts.len() - 1])
+    paths
+        .last()
+        .copied()
+        .ok_or_else(|| anyhow!("Cannot arrange adapters without any adapters"))
 }
 
-fn compute_paths(current: usize, previous: &[usize], paths: &[usize]) -> usize {
-    let mut result = 0usize;
-
-    for (i, x) in previous.iter().enumerate() {
-        if *x + 3 >= current {
-            result += paths[i];
-        }
-    }
-
-    result
+fn parse_jolts(input: &[&str]) -> anyhow::Result<Vec<usize>> {
+    let mut jolts = parse::lines::<usize>(input)?;
+    jolts.sort_unstable();
+    Ok(jolts)
 }
 
-fn compute_paths_initial(current: usize, previous: &[usize], paths: &[usize]) -> usize {
-    let result = compute_paths(current, previous, paths);
+fn compute_paths(
+    current: usize,
+    previous: &[usize],
+    paths: &[usize],
+    initial: usize,
+) -> Option<usize> {
+    let mut result = initial;
 
-    if current <= 3 {
-        result + 1
-    } else {
-        result
+    for (i, x) in previous.iter().enumerate() {
+        if current - x <= MAX_DIFFERENCE {
+            result = result.checked_add(paths[i])?;
+        }
     }
-}
 
-fn abs_diff(x: usize, y: usize) -> usize {
-    if x > y {
-        x - y
-    } else {
-        y - x
-    }
+    Some(result)
 }
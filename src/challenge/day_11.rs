@@ -1,12 +1,31 @@
+use super::grid::{Grid, ADJACENT};
+use super::simulation::{self, Cell, Shade, Simulation};
+use anyhow::anyhow;
+
 pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let mut layout = SeatLayoutBuilder::new(input).build(false);
-    while layout.simulate(false) {}
-    Ok(layout.count_occupied())
+    settle(SeatLayoutBuilder::new(input)?.build(false), false)
 }
 
 pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let mut layout = SeatLayoutBuilder::new(input).build(true);
-    while layout.simulate(true) {}
+    settle(SeatLayoutBuilder::new(input)?.build(true), true)
+}
+
+fn settle(mut layout: SeatLayout, extended: bool) -> anyhow::Result<usize> {
+    // All seats change at once, so some layouts keep going round in circles. Seats see each other
+    // both ways, and such layouts only ever flip between two states, so comparing with the one
+    // before the previous is enough.
+    let mut before = Vec::new();
+    let mut previous = layout.occupied.clone();
+
+    while layout.simulate(extended) {
+        if layout.occupied == before {
+            return Err(anyhow!("The seats never settle"));
+        }
+
+        std::mem::swap(&mut before, &mut previous);
+        previous.clone_from(&layout.occupied);
+    }
+
     Ok(layout.count_occupied())
 }
 
@@ -72,242 +91,86 @@ impl SeatLayout {
     }
 }
 
-struct SeatLayoutBuilder {
-    width: usize,
-    height: usize,
-    seats: Vec<bool>,
-}
+struct SeatLayoutBuilder(Grid<bool>);
 
 impl SeatLayoutBuilder {
-    fn new(input: &[&str]) -> Self {
-        let width = input[0].len();
-        let height = input.len();
-
-        let seats = input
-            .iter()
-            .flat_map(|line| line.bytes())
-            .map(|byte| byte == b'L')
-            .collect::<Vec<_>>();
-
-        SeatLayoutBuilder {
-            width,
-            height,
-            seats,
-        }
+    fn new(input: &[&str]) -> anyhow::Result<Self> {
+        Ok(SeatLayoutBuilder(Grid::parse(input, |byte| byte == b'L')?))
     }
 
     fn build(&self, extended: bool) -> SeatLayout {
-        let mut seats = Vec::with_capacity(self.seats.len());
+        let grid = &self.0;
+        let mut seats = Vec::with_capacity(grid.cells().len());
 
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let index = x + y * self.width;
+        for (x, y) in grid.positions().filter(|(x, y)| grid[(*x, *y)]) {
+            let mut neighbors = [grid.cells().len(); 8];
 
-                if self.seats[index] {
-                    let neighbors = if extended {
-                        self.find_visible_neighbors(x, y, index)
-                    } else {
-                        self.find_direct_neighbors(x, y, index)
-                    };
+            for (i, direction) in ADJACENT.iter().enumerate() {
+                let neighbor = if extended {
+                    grid.ray(x, y, *direction).find(|(x, y)| grid[(*x, *y)])
+                } else {
+                    grid.offset(x, y, *direction)
+                };
 
-                    seats.push(Seat::new(index, neighbors));
+                if let Some((x, y)) = neighbor {
+                    neighbors[i] = grid.index(x, y);
                 }
             }
-        }
 
-        SeatLayout::new(self.width, self.height, seats)
-    }
-
-    fn find_direct_neighbors(&self, x: usize, y: usize, index: usize) -> [usize; 8] {
-        let mut count = 0;
-        let mut neighbors = [self.width * self.height; 8];
-
-        if y > 0 {
-            let index = index - self.width;
-
-            if x > 0 {
-                neighbors[count] = index - 1;
-                count += 1;
-            }
-
-            neighbors[count] = index;
-            count += 1;
-
-            if x + 1 < self.width {
-                neighbors[count] = index + 1;
-                count += 1;
-            }
-        }
-
-        if x > 0 {
-            neighbors[count] = index - 1;
-            count += 1;
-        }
-
-        if x + 1 < self.width {
-            neighbors[count] = index + 1;
-            count += 1;
+            seats.push(Seat::new(grid.index(x, y), neighbors));
         }
 
-        if y + 1 < self.height {
-            let index = index + self.width;
-
-            if x > 0 {
-                neighbors[count] = index - 1;
-                count += 1;
-            }
-
-            neighbors[count] = index;
-            count += 1;
-
-            if x + 1 < self.width {
-                neighbors[count] = index + 1;
-            }
-        }
-
-        neighbors
+        SeatLayout::new(grid.width(), grid.height(), seats)
     }
+}
 
-    fn find_visible_neighbors(&self, x: usize, y: usize, index: usize) -> [usize; 8] {
-        let mut count = 0;
-        let mut neighbors = [self.width * self.height; 8];
-
-        {
-            let mut x = x;
-            let mut y = y;
-            let mut index = index;
-
-            while x > 0 && y > 0 {
-                x -= 1;
-                y -= 1;
-                index -= self.width + 1;
-
-                if self.seats[index] {
-                    neighbors[count] = index;
-                    count += 1;
-                    break;
-                }
-            }
-        }
-
-        {
-            let mut y = y;
-            let mut index = index;
-
-            while y > 0 {
-                y -= 1;
-                index -= self.width;
-
-                if self.seats[index] {
-                    neighbors[count] = index;
-                    count += 1;
-                    break;
-                }
-            }
-        }
-
-        {
-            let mut x = x;
-            let mut y = y;
-            let mut index = index;
-
-            while x + 1 < self.width && y > 0 {
-                x += 1;
-                y -= 1;
-                index -= self.width - 1;
-
-                if self.seats[index] {
-                    neighbors[count] = index;
-                    count += 1;
-                    break;
-                }
-            }
-        }
-
-        {
-            let mut x = x;
-            let mut index = index;
-
-            while x > 0 {
-                x -= 1;
-                index -= 1;
-
-                if self.seats[index] {
-                    neighbors[count] = index;
-                    count += 1;
-                    break;
-                }
-            }
-        }
-
-        {
-            let mut x = x;
-            let mut index = index;
-
-            while x + 1 < self.width {
-                x += 1;
-                index += 1;
-
-                if self.seats[index] {
-                    neighbors[count] = index;
-                    count += 1;
-                    break;
-                }
-            }
-        }
-
-        {
-            let mut x = x;
-            let mut y = y;
-            let mut index = index;
-
-            while x > 0 && y + 1 < self.height {
-                x -= 1;
-                y += 1;
-                index += self.width - 1;
+pub fn simulation(input: &[&str], extended: bool) -> anyhow::Result<Box<dyn Simulation>> {
+    let builder = SeatLayoutBuilder::new(input)?;
+    let layout = builder.build(extended);
 
-                if self.seats[index] {
-                    neighbors[count] = index;
-                    count += 1;
-                    break;
-                }
-            }
-        }
+    Ok(Box::new(SeatSimulation {
+        previous: layout.occupied.clone(),
+        layout,
+        extended,
+        seats: builder.0,
+    }))
+}
 
-        {
-            let mut y = y;
-            let mut index = index;
+struct SeatSimulation {
+    layout: SeatLayout,
+    extended: bool,
+    seats: Grid<bool>,
+    previous: Vec<bool>,
+}
 
-            while y + 1 < self.height {
-                y += 1;
-                index += self.width;
+impl Simulation for SeatSimulation {
+    fn step(&mut self) -> bool {
+        self.previous.clone_from(&self.layout.occupied);
+        self.layout.simulate(self.extended)
+    }
 
-                if self.seats[index] {
-                    neighbors[count] = index;
-                    count += 1;
-                    break;
+    fn render(&self, _: &[isize]) -> Grid<Cell> {
+        let cells = self
+            .seats
+            .cells()
+            .iter()
+            .enumerate()
+            .map(|(index, seat)| {
+                let occupied = self.layout.occupied[index];
+
+                match (seat, occupied) {
+                    (false, _) => Cell::new('.', Shade::Background),
+                    (true, true) => Cell::new('#', simulation::shade(true, self.previous[index])),
+                    (true, false) => Cell::new('L', simulation::shade(false, self.previous[index])),
                 }
-            }
-        }
-
-        {
-            let mut x = x;
-            let mut y = y;
-            let mut index = index;
+            })
+            .collect();
 
-            while x + 1 < self.width && y + 1 < self.height {
-                x += 1;
-                y += 1;
-                index += self.width + 1;
-
-                if self.seats[index] {
-                    neighbors[count] = index;
-                    break;
-                }
-            }
-        }
+        Grid::new(self.seats.width(), self.seats.height(), cells)
+    }
 
-        neighbors
+    fn status(&self) -> String {
+        format!("{} occupied seats", self.layout.count_occupied())
     }
 }
 
@@ -1,13 +1,12 @@
-use anyhow::anyhow;
+use super::parse;
+use anyhow::{anyhow, Context};
 
 pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
     let mut east = 0isize;
     let mut north = 0isize;
     let mut direction = Direction::East;
 
-    for line in input {
-        let (action, amount) = Action::parse(line)?;
-
+    for (action, amount) in parse::lines_with(input, Action::parse)? {
         match action {
             Action::East => east += amount,
             Action::South => north -= amount,
@@ -33,9 +32,7 @@ pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
     let mut ship_east = 0isize;
     let mut ship_north = 0isize;
 
-    for line in input {
-        let (action, amount) = Action::parse(line)?;
-
+    for (action, amount) in parse::lines_with(input, Action::parse)? {
         match action {
             Action::East => waypoint_east += amount,
             Action::South => waypoint_north -= amount,
@@ -44,8 +41,14 @@ pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
             Action::Left => rotate_point(&mut waypoint_north, &mut waypoint_east, 360 - amount),
             Action::Right => rotate_point(&mut waypoint_north, &mut waypoint_east, amount),
             Action::Forward => {
-                ship_east += waypoint_east * amount;
-                ship_north += waypoint_north * amount;
+                ship_east = waypoint_east
+                    .checked_mul(amount)
+                    .and_then(|east| ship_east.checked_add(east))
+                    .context("The ship sails out of range")?;
+                ship_north = waypoint_north
+                    .checked_mul(amount)
+                    .and_then(|north| ship_north.checked_add(north))
+                    .context("The ship sails out of range")?;
             }
         }
     }
@@ -85,19 +88,29 @@ enum Action {
 
 impl Action {
     fn parse(string: &str) -> anyhow::Result<(Self, isize)> {
-        let action = match string.as_bytes()[0] {
-            b'E' => Action::East,
-            b'S' => Action::South,
-            b'W' => Action::West,
-            b'N' => Action::North,
-            b'L' => Action::Left,
-            b'R' => Action::Right,
-            b'F' => Action::Forward,
+        let mut chars = string.chars();
+
+        let action = match chars.next() {
+            Some('E') => Action::East,
+            Some('S') => Action::South,
+            Some('W') => Action::West,
+            Some('N') => Action::North,
+            Some('L') => Action::Left,
+            Some('R') => Action::Right,
+            Some('F') => Action::Forward,
             _ => return Err(anyhow!("Invalid action {}", string)),
         };
 
-        let amount = string[1..].parse()?;
-        Ok((action, amount))
+        // Small enough that the sums of a whole route cannot overflow
+        let amount = parse::value::<i32>(chars.as_str())? as isize;
+
+        match action {
+            Action::Left | Action::Right if amount % 90 != 0 => {
+                Err(anyhow!("Cannot turn by {} degrees", amount))
+            }
+            Action::Left | Action::Right => Ok((action, amount.rem_euclid(360))),
+            _ => Ok((action, amount)),
+        }
     }
 }
 
@@ -1,44 +1,94 @@
-pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let arrived_at = input[0].parse::<usize>()?;
-
-    let mut best_id = 0;
-    let mut min_wait_time = usize::MAX;
+use super::parse;
+use anyhow::{anyhow, Context};
+use std::num::{NonZeroU64, NonZeroUsize};
 
-    for id in input[1].split(',') {
-        if id.starts_with('x') {
-            continue;
-        }
+pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
+    let arrived_at = parse::line::<usize>(input, 0)?;
+    let ids = parse::line_with(input, 1, parse_ids::<NonZeroUsize>)?;
 
-        let id = id.parse::<usize>()?;
-        let wait_time = (id - (arrived_at % id)) % id;
+    let (best_id, min_wait_time) = ids
+        .into_iter()
+        .flatten()
+        .map(|id| (id.get(), (id.get() - (arrived_at % id)) % id))
+        .min_by_key(|(_, wait_time)| *wait_time)
+        .context("Cannot find any bus in service")?;
 
-        if wait_time < min_wait_time {
-            best_id = id;
-            min_wait_time = wait_time;
-        }
-    }
-
-    Ok(best_id * min_wait_time)
+    best_id
+        .checked_mul(min_wait_time)
+        .context("The answer is too large")
 }
 
 pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let mut time = 0u64;
-    let mut step = 1u64;
+    let mut time = 0u128;
+    let mut step = 1u128;
 
-    for (i, id) in input[1].split(',').enumerate() {
-        if id.starts_with('x') {
+    let ids = parse::line_with(input, 1, parse_ids::<NonZeroU64>)?;
+
+    for (i, id) in ids.into_iter().enumerate() {
+        let Some(id) = id else {
             continue;
-        }
+        };
 
-        let id = id.parse::<u64>()?;
-        let offset = i as u64;
+        // Waiting another k steps has to make up for how early the bus currently departs, i.e.
+        // k * step = remainder (mod id), where the ids may share factors
+        let id = id.get() as u128;
+        let remainder = (id - (time % id + i as u128 % id) % id) % id;
+        let factor = gcd(step % id, id);
 
-        while (time + offset) % id != 0 {
-            time += step;
+        if !remainder.is_multiple_of(factor) {
+            return Err(anyhow!(
+                "Bus {} can never depart {} minutes after the first",
+                id,
+                i
+            ));
         }
 
-        step *= id;
+        let modulus = id / factor;
+        let k = remainder / factor * inverse(step % id / factor, modulus) % modulus;
+
+        let next_step = step
+            .checked_mul(modulus)
+            .context("The departure time is too large")?;
+        time += k * step;
+        step = next_step;
     }
 
     Ok(time)
 }
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+// Modular inverse by the extended Euclidean algorithm, the value and modulus have to be coprime
+fn inverse(value: u128, modulus: u128) -> u128 {
+    let (mut r0, mut r1) = (modulus as i128, (value % modulus) as i128);
+    let (mut t0, mut t1) = (0i128, 1i128);
+
+    while r1 != 0 {
+        let quotient = r0 / r1;
+        (r0, r1) = (r1, r0 - quotient * r1);
+        (t0, t1) = (t1, t0 - quotient * t1);
+    }
+
+    t0.rem_euclid(modulus as i128) as u128
+}
+
+// Buses which are out of service are listed as `x`
+fn parse_ids<T>(line: &str) -> anyhow::Result<Vec<Option<T>>>
+where
+    T: std::str::FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    parse::list_with(line, ',', |id| {
+        if id.starts_with('x') {
+            Ok(None)
+        } else {
+            parse::value(id).map(Some)
+        }
+    })
+}
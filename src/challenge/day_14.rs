@@ -1,7 +1,10 @@
-use anyhow::Context;
+use super::parse;
+use anyhow::{anyhow, Context};
 use std::collections::HashMap;
 
 const BIT_COUNT: usize = 36;
+// Every floating bit doubles the number of writes
+const MAX_FLOATING_BITS: usize = 16;
 
 pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
     let mut memory = HashMap::new();
@@ -9,28 +12,25 @@ pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
     let mut set_mask = u64::MIN;
     let mut unset_mask = u64::MAX;
 
-    for line in input {
-        let (left, right) = line
-            .split_once('=')
-            .context("Unexpected end of line, expecting `=`")?;
-
-        if left.len() == 5 {
-            set_mask = u64::MIN;
-            unset_mask = u64::MIN;
-
-            for (i, byte) in right.as_bytes()[1..].iter().enumerate() {
-                match *byte {
-                    b'0' => unset_mask |= 1 << (BIT_COUNT - 1 - i),
-                    b'1' => set_mask |= 1 << (BIT_COUNT - 1 - i),
-                    _ => {}
+    for command in parse::lines_with(input, Command::parse)? {
+        match command {
+            Command::Mask(mask) => {
+                set_mask = u64::MIN;
+                unset_mask = u64::MIN;
+
+                for (i, byte) in mask.iter().enumerate() {
+                    match *byte {
+                        b'0' => unset_mask |= 1 << (BIT_COUNT - 1 - i),
+                        b'1' => set_mask |= 1 << (BIT_COUNT - 1 - i),
+                        _ => {}
+                    }
                 }
-            }
 
-            unset_mask = !unset_mask;
-        } else {
-            let position = &left[4..left.len() - 2];
-            let value = right[1..].parse::<u64>()?;
-            memory.insert(position, (value | set_mask) & unset_mask);
+                unset_mask = !unset_mask;
+            }
+            Command::Write(position, value) => {
+                memory.insert(position, (value | set_mask) & unset_mask);
+            }
         }
     }
 
@@ -43,26 +43,31 @@ pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
     let mut bit_mask = u64::MIN;
     let mut floating_masks = Vec::<u64>::with_capacity(BIT_COUNT);
 
-    for line in input {
-        let (left, right) = line
-            .split_once('=')
-            .context("Unexpected end of line, expecting `=`")?;
-
-        if left.len() == 5 {
-            bit_mask = u64::MIN;
-            floating_masks.clear();
+    for command in parse::lines_with(input, Command::parse)? {
+        match command {
+            Command::Mask(mask) => {
+                bit_mask = u64::MIN;
+                floating_masks.clear();
+
+                for (i, byte) in mask.iter().enumerate() {
+                    match *byte {
+                        b'1' => bit_mask |= 1 << (BIT_COUNT - 1 - i),
+                        b'X' => floating_masks.push(1 << (BIT_COUNT - 1 - i)),
+                        _ => {}
+                    }
+                }
 
-            for (i, byte) in right.as_bytes()[1..].iter().enumerate() {
-                match *byte {
-                    b'1' => bit_mask |= 1 << (BIT_COUNT - 1 - i),
-                    b'X' => floating_masks.push(1 << (BIT_COUNT - 1 - i)),
-                    _ => {}
+                if floating_masks.len() > MAX_FLOATING_BITS {
+                    return Err(anyhow!(
+                        "Mask has {} floating bits, at most {} are supported",
+                        floating_masks.len(),
+                        MAX_FLOATING_BITS
+                    ));
                 }
             }
-        } else {
-            let position = left[4..left.len() - 2].parse::<u64>()? | bit_mask;
-            let value = right[1..].parse::<u64>()?;
-            update(position, &floating_masks, value, &mut memory);
+            Command::Write(position, value) => {
+                update(position | bit_mask, &floating_masks, value, &mut memory);
+            }
         }
     }
 
@@ -81,3 +86,34 @@ fn update(position: u64, masks: &[u64], value: u64, memory: &mut HashMap<u64, u6
     update(position | mask, masks, value, memory);
     update(position & !mask, masks, value, memory);
 }
+
+enum Command<'a> {
+    Mask(&'a [u8]),
+    Write(u64, u64),
+}
+
+impl<'a> Command<'a> {
+    fn parse(line: &'a str) -> anyhow::Result<Self> {
+        let (left, right) = parse::split_once(line, " = ")?;
+
+        if left == "mask" {
+            let mask = right.as_bytes();
+
+            if mask.len() != BIT_COUNT || !mask.iter().all(|x| matches!(x, b'0' | b'1' | b'X')) {
+                return Err(anyhow!("Expected {} bits of 0, 1 or X in mask", BIT_COUNT));
+            }
+
+            return Ok(Command::Mask(mask));
+        }
+
+        let position = left
+            .strip_prefix("mem[")
+            .and_then(|left| left.strip_suffix(']'))
+            .with_context(|| format!("Expected `mask` or `mem[<position>]`, found {:?}", left))?;
+
+        Ok(Command::Write(
+            parse::value(position)?,
+            parse::value(right)?,
+        ))
+    }
+}
@@ -1,33 +1,51 @@
+use super::parse;
+use anyhow::Context;
+use std::collections::HashMap;
+
 pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    play_until::<2020>(input[0])
+    play_until(&parse_starting_numbers(input)?, 2020)
 }
 
 pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    play_until::<30000000>(input[0])
+    play_until(&parse_starting_numbers(input)?, 30000000)
 }
 
-fn play_until<const N: usize>(starting_numbers: &str) -> anyhow::Result<usize> {
-    let mut last = 0usize;
-    let mut numbers = [usize::MAX; N];
+fn play_until(starting_numbers: &[usize], turns: usize) -> anyhow::Result<usize> {
+    let (&(mut last), previous) = starting_numbers
+        .split_last()
+        .context("Missing starting numbers")?;
 
-    for (i, number) in starting_numbers.split(',').enumerate() {
-        last = number.parse()?;
-        numbers[last] = i;
+    if let Some(number) = starting_numbers.get(turns.wrapping_sub(1)) {
+        return Ok(*number);
     }
 
-    numbers[last] = usize::MAX;
+    // Spoken numbers are ages, which stay below the number of turns, so only starting numbers
+    // can be larger
+    let mut numbers = vec![usize::MAX; turns];
+    let mut large_numbers = HashMap::new();
+
+    let mut speak = |number: usize, turn: usize| match numbers.get_mut(number) {
+        Some(time) => std::mem::replace(time, turn),
+        None => large_numbers.insert(number, turn).unwrap_or(usize::MAX),
+    };
+
+    for (i, number) in previous.iter().enumerate() {
+        speak(*number, i);
+    }
 
-    for i in numbers.len()..(N - 1) {
-        let time = numbers[last];
+    for i in previous.len()..turns - 1 {
+        let time = speak(last, i);
 
         if time == usize::MAX {
             last = 0;
         } else {
             last = i - time;
         }
-
-        numbers[last] = i;
     }
 
     Ok(last)
 }
+
+fn parse_starting_numbers(input: &[&str]) -> anyhow::Result<Vec<usize>> {
+    parse::line_with(input, 0, |line| parse::list(line, ','))
+}
@@ -1,21 +1,21 @@
-use anyhow::Context;
+use super::parse;
+use anyhow::{anyhow, Context};
+use std::fmt::Write;
 use std::ops::{Index, RangeInclusive};
 use std::str::FromStr;
 
 const FIELD_COUNT: usize = 20;
 const FIELDS_MASK: u32 = 0b11111111111111111111;
 const DEPARTURE_FIELD_COUNT: usize = 6;
-const MY_TICKET_OFFSET: usize = FIELD_COUNT + 2;
-const OTHER_TICKETS_OFFSET: usize = MY_TICKET_OFFSET + 3;
-const INVALID_RULE: &str = "Rule is invalid";
 
 pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let rules = parse_rules(&input[0..FIELD_COUNT])?;
+    let [rules, _, other_tickets] = parse::split_sections(input)?;
+    let rules = rules.lines::<Rule>()?;
     let mut error_rate = 0usize;
 
-    for ticket in &input[OTHER_TICKETS_OFFSET..] {
-        if let Some(error) = ticket.parse::<Ticket>()?.validate(&rules) {
-            error_rate += error;
+    for ticket in other_tickets.skip(1).lines::<Ticket>()? {
+        if let Some(error) = ticket.validate(&rules) {
+            error_rate += error as usize;
         }
     }
 
@@ -23,26 +23,70 @@ pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
 }
 
 pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let rules = parse_rules(&input[0..FIELD_COUNT])?;
+    let fields = resolve_fields(input)?;
+
+    fields.columns[..DEPARTURE_FIELD_COUNT]
+        .iter()
+        .try_fold(1usize, |product, column| {
+            product.checked_mul(fields.my_ticket[*column] as usize)
+        })
+        .context("The answer is too large")
+}
+
+pub fn explain(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
+    let fields = resolve_fields(input)?;
+    let mut output = String::new();
+
+    for (rule, column) in fields.rules.iter().zip(fields.columns) {
+        writeln!(
+            output,
+            "{}: column {}, {} on your ticket",
+            rule.name,
+            column + 1,
+            fields.my_ticket[column]
+        )?;
+    }
+
+    Ok(output)
+}
+
+struct Fields {
+    rules: Vec<Rule>,
+    my_ticket: Ticket,
+    // Column of every rule, in the order of the rules
+    columns: [usize; FIELD_COUNT],
+}
+
+fn resolve_fields(input: &[&str]) -> anyhow::Result<Fields> {
+    let [rules, my_ticket, other_tickets] = parse::split_sections(input)?;
+    let rules = rules.lines::<Rule>()?;
+
+    if rules.len() != FIELD_COUNT {
+        return Err(anyhow!(
+            "Expected {} rules, found {}",
+            FIELD_COUNT,
+            rules.len()
+        ));
+    }
+
     let mut possible_fields = [FIELDS_MASK; FIELD_COUNT];
 
-    let my_ticket = input[MY_TICKET_OFFSET].parse::<Ticket>()?;
+    let my_ticket = my_ticket.skip(1).line::<Ticket>(0)?;
     check_ticket(&my_ticket, &rules, &mut possible_fields);
 
-    for ticket in &input[OTHER_TICKETS_OFFSET..] {
-        let ticket = ticket.parse::<Ticket>()?;
-
+    for ticket in other_tickets.skip(1).lines::<Ticket>()? {
         if ticket.validate(&rules).is_none() {
             check_ticket(&ticket, &rules, &mut possible_fields);
         }
     }
 
-    let departure_field_lookup = build_departure_field_lookup(&possible_fields);
+    let columns = build_field_lookup(&possible_fields)?;
 
-    Ok(departure_field_lookup
-        .iter()
-        .map(|index| my_ticket[*index])
-        .product::<usize>())
+    Ok(Fields {
+        rules,
+        my_ticket,
+        columns,
+    })
 }
 
 fn check_ticket(ticket: &Ticket, rules: &[Rule], possible_fields: &mut [u32; FIELD_COUNT]) {
@@ -55,9 +99,9 @@ fn check_ticket(ticket: &Ticket, rules: &[Rule], possible_fields: &mut [u32; FIE
     }
 }
 
-fn build_departure_field_lookup(
+fn build_field_lookup(
     possible_fields: &[u32; FIELD_COUNT],
-) -> [usize; DEPARTURE_FIELD_COUNT] {
+) -> anyhow::Result<[usize; FIELD_COUNT]> {
     let mut index = 0usize;
 
     let mut lookup = possible_fields.map(|mask| {
@@ -66,50 +110,45 @@ fn build_departure_field_lookup(
         pair
     });
 
-    lookup.sort_unstable_by(|(_, x), (_, y)| x.cmp(y));
+    lookup.sort_unstable_by_key(|(_, count)| *count);
 
     let mut matched_fields_mask = 0u32;
-    let mut departure_field_lookup = [0usize; DEPARTURE_FIELD_COUNT];
+    let mut field_lookup = [0usize; FIELD_COUNT];
 
     for (rule_index, _) in lookup {
-        // assumes we solved the problem and mark has one bit
         let mask = possible_fields[rule_index] & !matched_fields_mask;
-        matched_fields_mask |= mask;
 
-        if rule_index < DEPARTURE_FIELD_COUNT {
-            departure_field_lookup[rule_index] = mask.trailing_zeros() as usize;
+        if mask.count_ones() != 1 {
+            return Err(anyhow!(
+                "Cannot find a unique field for rule {}",
+                rule_index + 1
+            ));
         }
-    }
 
-    departure_field_lookup
-}
-
-fn parse_rules(rules: &[&str]) -> anyhow::Result<Vec<Rule>> {
-    let rules = rules
-        .iter()
-        .map(|line| line.parse())
-        .collect::<Result<Vec<Rule>, _>>()?;
+        matched_fields_mask |= mask;
+        field_lookup[rule_index] = mask.trailing_zeros() as usize;
+    }
 
-    Ok(rules)
+    Ok(field_lookup)
 }
 
-struct Ticket([usize; FIELD_COUNT]);
+struct Ticket([u32; FIELD_COUNT]);
 
 impl Ticket {
-    fn validate(&self, rules: &[Rule]) -> Option<usize> {
+    fn validate(&self, rules: &[Rule]) -> Option<u32> {
         self.0
             .iter()
             .find(|value| !rules.iter().any(|rule| rule.matches(value)))
             .cloned()
     }
 
-    fn iter(&self) -> impl Iterator<Item = &usize> {
+    fn iter(&self) -> impl Iterator<Item = &u32> {
         self.0.iter()
     }
 }
 
 impl Index<usize> for Ticket {
-    type Output = usize;
+    type Output = u32;
 
     fn index(&self, index: usize) -> &Self::Output {
         &self.0[index]
@@ -120,21 +159,26 @@ impl FromStr for Ticket {
     type Err = anyhow::Error;
 
     fn from_str(ticket: &str) -> Result<Self, Self::Err> {
-        let mut values = [0usize; FIELD_COUNT];
+        let values = parse::list(ticket, ',')?;
+        let count = values.len();
 
-        for (i, value) in ticket.split(',').enumerate() {
-            values[i] = value.parse()?;
-        }
+        let values = values
+            .try_into()
+            .map_err(|_| anyhow!("Expected {} values, found {}", FIELD_COUNT, count))?;
 
         Ok(Ticket(values))
     }
 }
 
-struct Rule(RangeInclusive<usize>, RangeInclusive<usize>);
+struct Rule {
+    name: String,
+    first: RangeInclusive<u32>,
+    second: RangeInclusive<u32>,
+}
 
 impl Rule {
-    fn matches(&self, value: &usize) -> bool {
-        self.0.contains(value) || self.1.contains(value)
+    fn matches(&self, value: &u32) -> bool {
+        self.first.contains(value) || self.second.contains(value)
     }
 }
 
@@ -142,12 +186,18 @@ impl FromStr for Rule {
     type Err = anyhow::Error;
 
     fn from_str(line: &str) -> Result<Self, Self::Err> {
-        let line = &line.split_once(':').context(INVALID_RULE)?.1;
-
-        let (a, line) = line[1..].split_once('-').context(INVALID_RULE)?;
-        let (b, line) = line.split_once(' ').context(INVALID_RULE)?;
-        let (c, d) = line[3..].split_once('-').context(INVALID_RULE)?;
-
-        Ok(Rule(a.parse()?..=b.parse()?, c.parse()?..=d.parse()?))
+        let (name, ranges) = parse::split_once(line, ": ")?;
+        let (first, second) = parse::split_once(ranges, " or ")?;
+
+        Ok(Rule {
+            name: name.to_string(),
+            first: parse_range(first)?,
+            second: parse_range(second)?,
+        })
     }
 }
+
+fn parse_range(range: &str) -> anyhow::Result<RangeInclusive<u32>> {
+    let (start, end) = parse::split_once(range, "-")?;
+    Ok(parse::value(start)?..=parse::value(end)?)
+}
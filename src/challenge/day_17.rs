@@ -1,12 +1,16 @@
-use std::iter::Iterator;
+use super::grid::{Grid, Space};
+use super::simulation::{self, Cell, Simulation};
+use std::ops::RangeInclusive;
 
-const SIZE: isize = 8;
-const PADDING: isize = 6;
+const CYCLES: usize = 6;
+
+// Cubes can grow one step per cycle, plus a boundary which is never simulated
+const PADDING: usize = CYCLES + 1;
 
 pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let mut pocket_dimension = PocketDimension::<3, { get_volume(3) }>::new(input);
+    let mut pocket_dimension = PocketDimension::<3>::new(input)?;
 
-    for _ in 0..6 {
+    for _ in 0..CYCLES {
         pocket_dimension.simulate();
     }
 
@@ -14,139 +18,155 @@ pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
 }
 
 pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let mut pocket_dimension = PocketDimension::<4, { get_volume(4) }>::new(input);
+    let mut pocket_dimension = PocketDimension::<4>::new(input)?;
 
-    for _ in 0..6 {
+    for _ in 0..CYCLES {
         pocket_dimension.simulate();
     }
 
     Ok(pocket_dimension.count)
 }
 
-#[derive(Eq, PartialEq, Clone)]
-struct Point<const D: usize>([isize; D]);
-
-impl<const D: usize> Point<D> {
-    fn index(&self) -> usize {
-        self.0
-            .iter()
-            .enumerate()
-            .rev()
-            .fold(0, |sum, (index, value)| sum * get_length(index) + value) as usize
-    }
-
-    fn fill_neighbors(&self, points: &mut Vec<Self>) {
-        points.clear();
-        let this = self.clone();
-        this.fill_neighbors_at_index(self, points, 0);
-    }
-
-    fn fill_neighbors_at_index(self, origin: &Self, points: &mut Vec<Self>, index: usize) {
-        if index < D {
-            let min = (self.0[index] - 1).max(0);
-            let max = (self.0[index] + 1).min(get_length(index) - 1);
-
-            for value in min..=max {
-                self.update(index, value)
-                    .fill_neighbors_at_index(origin, points, index + 1);
-            }
-        } else if origin != &self {
-            points.push(self);
-        }
-    }
-
-    fn update(&self, index: usize, value: isize) -> Self {
-        let mut values = self.0;
-        values[index] = value;
-        Point(values)
+pub fn simulation(input: &[&str], four_dimensions: bool) -> anyhow::Result<Box<dyn Simulation>> {
+    if four_dimensions {
+        Ok(Box::new(PocketSimulation::<4>::new(input)?))
+    } else {
+        Ok(Box::new(PocketSimulation::<3>::new(input)?))
     }
 }
 
-struct PocketDimension<const D: usize, const V: usize> {
+struct PocketDimension<const D: usize> {
     count: usize,
-    cubes: [bool; V],
-    buffer: [bool; V],
+    cubes: Vec<bool>,
+    buffer: Vec<bool>,
+    space: Space<D>,
+    interior: Vec<usize>,
+    offsets: Vec<isize>,
 }
 
-impl<const D: usize, const V: usize> PocketDimension<D, V> {
-    fn new(rows: &[&str]) -> Self {
+impl<const D: usize> PocketDimension<D> {
+    fn new(rows: &[&str]) -> anyhow::Result<Self> {
+        let slice = Grid::parse(rows, |state| state == b'#')?;
+
+        let mut sizes = [2 * PADDING + 1; D];
+        sizes[0] = slice.width() + 2 * PADDING;
+        sizes[1] = slice.height() + 2 * PADDING;
+
+        let space = Space::new(sizes);
         let mut count = 0;
-        let mut cubes = [false; V];
-        let buffer = [false; V];
-
-        for (dy, row) in rows.iter().enumerate() {
-            let y = dy as isize + PADDING;
-
-            for (dx, state) in row.bytes().enumerate() {
-                if state == b'#' {
-                    let mut values = [PADDING; D];
-                    values[0] = dx as isize + PADDING;
-                    values[1] = y;
-                    let point = Point(values);
-                    count += 1;
-                    cubes[point.index()] = true;
-                }
-            }
+        let mut cubes = vec![false; space.len()];
+
+        for (x, y) in slice.positions().filter(|(x, y)| slice[(*x, *y)]) {
+            let mut point = [PADDING; D];
+            point[0] += x;
+            point[1] += y;
+            count += 1;
+            cubes[space.index(point)] = true;
         }
 
-        PocketDimension {
+        Ok(PocketDimension {
             count,
+            buffer: cubes.clone(),
             cubes,
-            buffer,
-        }
+            interior: space.interior().collect(),
+            offsets: space.neighbor_offsets(),
+            space,
+        })
     }
 
     fn simulate(&mut self) {
         self.count = 0;
-        self.buffer.fill(false);
-
-        let mut neighbors = Vec::with_capacity(D.pow(D as u32) - 1);
-        self.update_at_index(0, Point([0; D]), &mut neighbors);
 
-        std::mem::swap(&mut self.cubes, &mut self.buffer)
-    }
-
-    fn update_at_index(&mut self, index: usize, point: Point<D>, neighbors: &mut Vec<Point<D>>) {
-        if index == D {
-            let index = point.index();
-            point.fill_neighbors(neighbors);
-
-            let count = neighbors
+        for index in &self.interior {
+            let count = self
+                .offsets
                 .iter()
-                .filter(|neighbor| self.cubes[neighbor.index()])
+                .filter(|offset| self.cubes[index.wrapping_add_signed(**offset)])
                 .take(4)
                 .count();
 
-            if self.cubes[index] {
-                if count == 2 || count == 3 {
-                    self.count += 1;
-                    self.buffer[index] = true;
-                }
-            } else if count == 3 {
-                self.count += 1;
-                self.buffer[index] = true;
-            }
-        } else {
-            for value in 0..get_length(index) {
-                self.update_at_index(index + 1, point.update(index, value), neighbors);
-            }
+            let active = count == 3 || self.cubes[*index] && count == 2;
+            self.count += active as usize;
+            self.buffer[*index] = active;
         }
+
+        std::mem::swap(&mut self.cubes, &mut self.buffer)
     }
 }
 
-const fn get_length(index: usize) -> isize {
-    let base = if index < 2 { SIZE } else { 1 };
-    base + 2 * PADDING
+struct PocketSimulation<const D: usize> {
+    pocket_dimension: PocketDimension<D>,
+    cycle: usize,
 }
 
-const fn get_volume(dimensions: usize) -> usize {
-    let mut area = 1usize;
-    let mut index = 0;
+impl<const D: usize> PocketSimulation<D> {
+    fn new(input: &[&str]) -> anyhow::Result<Self> {
+        Ok(PocketSimulation {
+            pocket_dimension: PocketDimension::new(input)?,
+            cycle: 0,
+        })
+    }
+}
+
+impl<const D: usize> Simulation for PocketSimulation<D> {
+    fn step(&mut self) -> bool {
+        // The padding only leaves room to grow for the cycles of the puzzle
+        if self.cycle == CYCLES {
+            return false;
+        }
+
+        self.pocket_dimension.simulate();
+        self.cycle += 1;
+        true
+    }
+
+    fn slices(&self) -> Vec<RangeInclusive<isize>> {
+        self.pocket_dimension.space.sizes()[2..]
+            .iter()
+            .map(|size| {
+                let extent = (size / 2) as isize;
+                -extent..=extent
+            })
+            .collect()
+    }
+
+    fn render(&self, slice: &[isize]) -> Grid<Cell> {
+        let PocketDimension {
+            cubes,
+            buffer,
+            space,
+            ..
+        } = &self.pocket_dimension;
+
+        let [width, height] = [space.sizes()[0], space.sizes()[1]];
+        let mut point = [PADDING; D];
+
+        for (value, coordinate) in point[2..].iter_mut().zip(slice) {
+            *value = value.wrapping_add_signed(*coordinate);
+        }
+
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                point[0] = x;
+                point[1] = y;
+
+                let index = space.index(point);
+                let active = cubes[index];
+                let symbol = if active { '#' } else { '.' };
 
-    while index < dimensions {
-        area *= get_length(index) as usize;
-        index += 1;
+                // The buffer holds the previous cycle, before the first cycle it is a copy
+                Cell::new(symbol, simulation::shade(active, buffer[index]))
+            })
+            .collect();
+
+        Grid::new(width, height, cells)
     }
 
-    area
+    fn status(&self) -> String {
+        format!(
+            "cycle {} of {}, {} active cubes",
+            self.cycle, CYCLES, self.pocket_dimension.count
+        )
+    }
 }
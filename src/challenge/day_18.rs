@@ -1,21 +1,30 @@
+use super::parse;
+use anyhow::{anyhow, Context};
+
+// Every parenthesis recurses, so the depth is limited to keep the stack in check
+const MAX_DEPTH: usize = 64;
+
 pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    Ok(solve(input, false))
+    solve(input, false)
 }
 
 pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    Ok(solve(input, true))
+    solve(input, true)
 }
 
-fn solve(input: &[&str], prioritize_sum: bool) -> u64 {
-    input
+fn solve(input: &[&str], prioritize_sum: bool) -> anyhow::Result<u64> {
+    let results = parse::lines_with(input, |line| Calculator::evaluate(line, prioritize_sum))?;
+
+    results
         .iter()
-        .map(|line| Calculator::evaluate(line, prioritize_sum).unwrap())
-        .sum::<u64>()
+        .try_fold(0u64, |sum, result| sum.checked_add(*result))
+        .context("Sum is too large")
 }
 
 struct Calculator<'a> {
     input: &'a str,
     prioritize_sum: bool,
+    depth: usize,
 }
 
 impl Calculator<'_> {
@@ -23,6 +32,7 @@ impl Calculator<'_> {
         let mut calculator = Calculator {
             input,
             prioritize_sum,
+            depth: 0,
         };
 
         calculator.evaluate_expression()
@@ -31,22 +41,25 @@ impl Calculator<'_> {
     fn evaluate_expression(&mut self) -> anyhow::Result<u64> {
         let mut lhs = self.evaluate_sum()?;
 
-        while !self.input.is_empty() {
-            let operator = self.input.as_bytes()[0];
-
-            if operator == b')' {
-                self.input = self.input[1..].trim_start_matches(' ');
-                break;
-            }
-
-            self.input = &self.input[2..];
-            let rhs = self.evaluate_sum()?;
+        while let Some(operator) = self.input.chars().next() {
+            self.input = self.input[operator.len_utf8()..].trim_start_matches(' ');
+
+            let result = match operator {
+                ')' if self.depth == 0 => return Err(anyhow!("Unexpected closing parenthesis")),
+                ')' => {
+                    self.depth -= 1;
+                    return Ok(lhs);
+                }
+                '+' => lhs.checked_add(self.evaluate_sum()?),
+                '*' => lhs.checked_mul(self.evaluate_sum()?),
+                _ => return Err(anyhow!("Unknown operator {:?}", operator)),
+            };
+
+            lhs = result.context("Result is too large")?;
+        }
 
-            match operator {
-                b'+' => lhs += rhs,
-                b'*' => lhs *= rhs,
-                _ => unreachable!(),
-            }
+        if self.depth > 0 {
+            return Err(anyhow!("Missing closing parenthesis"));
         }
 
         Ok(lhs)
@@ -59,9 +72,10 @@ impl Calculator<'_> {
             return Ok(lhs);
         }
 
-        while self.input.starts_with('+') {
-            self.input = &self.input[2..];
-            lhs += self.evaluate_number()?;
+        while let Some(input) = self.input.strip_prefix('+') {
+            self.input = input.trim_start_matches(' ');
+            let rhs = self.evaluate_number()?;
+            lhs = lhs.checked_add(rhs).context("Result is too large")?;
         }
 
         Ok(lhs)
@@ -69,7 +83,12 @@ impl Calculator<'_> {
 
     fn evaluate_number(&mut self) -> anyhow::Result<u64> {
         if let Some(input) = self.input.strip_prefix('(') {
-            self.input = input;
+            if self.depth == MAX_DEPTH {
+                return Err(anyhow!("Parentheses are nested too deeply"));
+            }
+
+            self.input = input.trim_start_matches(' ');
+            self.depth += 1;
             return self.evaluate_expression();
         }
 
@@ -81,6 +100,6 @@ impl Calculator<'_> {
         let value = &self.input[..index];
         self.input = self.input[index..].trim_start_matches(' ');
 
-        Ok(value.parse()?)
+        parse::value(value)
     }
 }
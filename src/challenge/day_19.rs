@@ -1,7 +1,13 @@
-use anyhow::Context;
+use super::parse;
+use anyhow::{anyhow, Context};
 use regex::Regex;
+use std::collections::HashMap;
 
 const MAX_LOOPS: usize = 10;
+// Rules can refer to others several times, so the pattern may grow exponentially with the input
+const MAX_PATTERN_LENGTH: usize = 1 << 20;
+
+type Rules<'a> = HashMap<usize, &'a str>;
 
 pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
     let (rules, input) = parse_regex_and_input(input, false)?;
@@ -17,31 +23,23 @@ fn parse_regex_and_input<'a>(
     input: &'a [&'a str],
     with_loops: bool,
 ) -> anyhow::Result<(Regex, &'a [&'a str])> {
-    let index = input
-        .iter()
-        .position(|line| line.is_empty())
-        .context("Input does not contain an empty line")?;
-
-    Ok((
-        parse_regex(&input[..index], with_loops)?,
-        &input[index + 1..],
-    ))
+    let [rules, messages] = parse::split_sections(input)?;
+    Ok((parse_regex(rules, with_loops)?, messages.lines))
 }
 
-fn parse_regex(input: &[&str], with_loops: bool) -> anyhow::Result<Regex> {
-    let mut rules = vec![""; input.len()];
-
-    for line in input {
-        let (id, line) = line
-            .split_once(':')
-            .with_context(|| format!("{} does not contain a :", line))?;
-
-        rules[id.parse::<usize>()?] = &line[1..];
-    }
+fn parse_regex(input: parse::Section<&str>, with_loops: bool) -> anyhow::Result<Regex> {
+    // Rule ids are not necessarily contiguous, e.g. in the examples
+    let rules = input
+        .lines_with(|line| {
+            let (id, rule) = parse::split_once(line, ": ")?;
+            Ok((parse::value::<usize>(id)?, rule))
+        })?
+        .into_iter()
+        .collect::<Rules>();
 
     let mut builder = String::new();
     builder.push('^');
-    build_rule(0, &rules, &mut builder, with_loops)?;
+    build_rule(0, &rules, &mut builder, with_loops, 0)?;
     builder.push('$');
 
     Ok(Regex::new(&builder)?)
@@ -49,34 +47,50 @@ fn parse_regex(input: &[&str], with_loops: bool) -> anyhow::Result<Regex> {
 
 fn build_rule(
     index: usize,
-    rules: &[&str],
+    rules: &Rules,
     builder: &mut String,
     with_loops: bool,
+    depth: usize,
 ) -> anyhow::Result<()> {
-    let rule = rules[index];
+    let rule = *rules
+        .get(&index)
+        .with_context(|| format!("Rule {} is not defined", index))?;
+
+    // Any longer chain of rules has to visit one of them twice
+    if depth > rules.len() {
+        return Err(anyhow!("Rule {} refers to itself", index));
+    }
 
     if with_loops {
         if index == 8 {
-            build_rule_8(rules, builder, with_loops)?;
+            build_rule_8(rules, builder, with_loops, depth)?;
             return Ok(());
         } else if index == 11 {
-            build_rule_11(rules, builder, with_loops)?;
+            build_rule_11(rules, builder, with_loops, depth)?;
             return Ok(());
         }
     }
 
+    if builder.len() > MAX_PATTERN_LENGTH {
+        return Err(anyhow!(
+            "Rules expand to more than {} bytes",
+            MAX_PATTERN_LENGTH
+        ));
+    }
+
     if rule.starts_with('"') {
-        builder.push(rule.chars().nth(1).context("Unexpected end of input")?);
+        let char = rule.chars().nth(1).context("Unexpected end of input")?;
+        builder.push_str(&regex::escape(char.encode_utf8(&mut [0; 4])));
         return Ok(());
     }
 
     match rule.split_once('|') {
-        None => build_pattern(rule, rules, builder, with_loops)?,
+        None => build_pattern(rule, rules, builder, with_loops, depth)?,
         Some((first, second)) => {
             builder.push('(');
-            build_pattern(first, rules, builder, with_loops)?;
+            build_pattern(first, rules, builder, with_loops, depth)?;
             builder.push('|');
-            build_pattern(second, rules, builder, with_loops)?;
+            build_pattern(second, rules, builder, with_loops, depth)?;
             builder.push(')');
         }
     }
@@ -86,30 +100,41 @@ fn build_rule(
 
 fn build_pattern(
     pattern: &str,
-    rules: &[&str],
+    rules: &Rules,
     builder: &mut String,
     with_loops: bool,
+    depth: usize,
 ) -> anyhow::Result<()> {
     for id in pattern.split_ascii_whitespace() {
-        build_rule(id.parse()?, rules, builder, with_loops)?;
+        build_rule(parse::value(id)?, rules, builder, with_loops, depth + 1)?;
     }
 
     Ok(())
 }
 
-fn build_rule_8(rules: &[&str], builder: &mut String, with_loops: bool) -> anyhow::Result<()> {
+fn build_rule_8(
+    rules: &Rules,
+    builder: &mut String,
+    with_loops: bool,
+    depth: usize,
+) -> anyhow::Result<()> {
     builder.push('(');
-    build_rule(42, rules, builder, with_loops)?;
+    build_rule(42, rules, builder, with_loops, depth + 1)?;
     builder.push(')');
     builder.push('+');
     Ok(())
 }
 
-fn build_rule_11(rules: &[&str], builder: &mut String, with_loops: bool) -> anyhow::Result<()> {
+fn build_rule_11(
+    rules: &Rules,
+    builder: &mut String,
+    with_loops: bool,
+    depth: usize,
+) -> anyhow::Result<()> {
     let mut builder_42 = String::new();
     let mut builder_31 = String::new();
-    build_rule(42, rules, &mut builder_42, with_loops)?;
-    build_rule(31, rules, &mut builder_31, with_loops)?;
+    build_rule(42, rules, &mut builder_42, with_loops, depth + 1)?;
+    build_rule(31, rules, &mut builder_31, with_loops, depth + 1)?;
 
     builder.push('(');
     build_rule_11_combination(1, &builder_42, &builder_31, builder);
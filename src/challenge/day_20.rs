@@ -1,183 +1,212 @@
-use anyhow::Context;
+use super::grid::{Grid, Orientation};
+use super::simulation::{Cell, Shade};
+use super::{parse, LineIndex};
+use anyhow::{anyhow, Context};
+use std::ops::Range;
 
 const SET_PIXEL: u8 = b'#';
 
 const TILE_SIZE: usize = 10;
 const FRAME_SIZE: usize = 8;
 
-const MONSTER_LENGTH: usize = 19;
-const MONSTER_PIXEL_COUNT: usize = 15;
-const MONSTER_TOP: [usize; 1] = [18];
-const MONSTER_MIDDLE: [usize; 8] = [0, 5, 6, 11, 12, 17, 18, 19];
-const MONSTER_BOTTOM: [usize; 6] = [1, 4, 7, 10, 13, 16];
-
-pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let tiles = parse_tiles(input);
-    let mut result = 1;
-
-    for (index, tile) in tiles.iter().enumerate() {
-        let count = tile
-            .edges
-            .iter()
-            .filter(|edge| find_match(index, edge.original, &tiles).is_some())
-            .count();
-
-        if count == 2 {
-            result *= tile.id;
-        }
+const MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+pub fn part_a(input: &[u8], lines: &LineIndex) -> anyhow::Result<impl std::fmt::Display> {
+    let tiles = parse_tiles(input, lines)?;
+    Ok(find_corners(&tiles).iter().product::<usize>())
+}
+
+pub fn part_b(input: &[u8], lines: &LineIndex) -> anyhow::Result<impl std::fmt::Display> {
+    let image = build_image(input, lines)?;
+    let monsters = find_sea_monsters(&image)?;
+
+    let mut pixels = image
+        .cells()
+        .iter()
+        .map(|pixel| *pixel == SET_PIXEL)
+        .collect::<Vec<_>>();
+
+    // Monsters can overlap, so every pixel is only removed once
+    for index in monsters {
+        pixels[index] = false;
     }
 
-    Ok(result)
+    Ok(pixels.iter().filter(|pixel| **pixel).count())
 }
 
-pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let image = build_image(input)?;
-    let monsters = count_monsters(&image);
+pub fn explain(input: &[u8], lines: &LineIndex) -> anyhow::Result<impl std::fmt::Display> {
+    let tiles = parse_tiles(input, lines)?;
+    let corners = find_corners(&tiles)
+        .iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>();
 
-    let pixels = image
+    let image = build_image(input, lines)?;
+    let monster_size = MONSTER
         .iter()
-        .flatten()
-        .filter(|pixel| **pixel == SET_PIXEL)
+        .flat_map(|row| row.bytes())
+        .filter(|pixel| *pixel == SET_PIXEL)
         .count();
-
-    Ok(pixels - monsters * MONSTER_PIXEL_COUNT)
+    let monsters = find_sea_monsters(&image)?.len() / monster_size;
+
+    Ok(format!(
+        "Corner tiles: {}\nImage: {}x{} pixels with {} sea monsters\n",
+        corners.join(", "),
+        image.width(),
+        image.height(),
+        monsters
+    ))
 }
 
-fn count_monsters(image: &Vec<Vec<u8>>) -> usize {
-    let mut count = 0;
-    let image_size = image.len();
+// The assembled image, with the pixels of the sea monsters marked like in the puzzle
+pub fn picture(input: &[u8], lines: &LineIndex) -> anyhow::Result<Grid<Cell>> {
+    let image = build_image(input, lines)?;
 
-    for y in 1..(image_size - 1) {
-        for x in 0..(image_size - MONSTER_LENGTH) {
-            if is_vertical_monster(x, y, image) {
-                count += 1;
+    let mut cells = image
+        .cells()
+        .iter()
+        .map(|pixel| {
+            if *pixel == SET_PIXEL {
+                Cell::new('#', Shade::Active)
+            } else {
+                Cell::new('.', Shade::Background)
             }
-        }
-    }
+        })
+        .collect::<Vec<_>>();
 
-    for y in 0..(image_size - MONSTER_LENGTH) {
-        for x in 1..(image_size - 1) {
-            if is_horizontal_monster(x, y, image) {
-                count += 1;
-            }
-        }
+    for index in find_sea_monsters(&image)? {
+        cells[index] = Cell::new('O', Shade::Highlight);
     }
 
-    count
+    Ok(Grid::new(image.width(), image.height(), cells))
 }
 
-fn is_horizontal_monster(x: usize, y: usize, image: &[Vec<u8>]) -> bool {
-    // Identifies the monster by checking for the middle of the body
-    // Then it does it checks if for the bottom of the body on both sides (in case it's flipped)
-    // Once it know if it's filled, it checks for the top of the body
-
-    // forward
-    (MONSTER_MIDDLE
+// Ids of the tiles with only two edges matching another tile
+fn find_corners(tiles: &[Tile]) -> Vec<usize> {
+    tiles
         .iter()
-        .all(|&index| image[y + index][x] == SET_PIXEL)
-        // original
-        && ((MONSTER_BOTTOM
-            .iter()
-            .all(|&index| image[y + index][x + 1] == SET_PIXEL)
-            && MONSTER_TOP
-                .iter()
-                .all(|&index| image[y + index][x - 1] == SET_PIXEL))
-            // flipped
-            || (MONSTER_BOTTOM
-                .iter()
-                .all(|&index| image[y + index][x - 1] == SET_PIXEL)
-                && MONSTER_TOP
-                    .iter()
-                    .all(|&index| image[y + index][x + 1] == SET_PIXEL))))
-        // backward
-        || (MONSTER_MIDDLE
-            .iter()
-            .all(|&index| image[y + MONSTER_LENGTH - index - 1][x] == SET_PIXEL)
-            // original
-            && ((MONSTER_BOTTOM
+        .enumerate()
+        .filter(|(index, tile)| {
+            let count = tile
+                .edges
                 .iter()
-                .all(|&index| image[y + MONSTER_LENGTH - index - 1][x + 1] == SET_PIXEL)
-                && MONSTER_TOP
-                    .iter()
-                    .all(|&index| image[y + MONSTER_LENGTH - index - 1][x - 1] == SET_PIXEL))
-                // flipped
-                || (MONSTER_BOTTOM
-                    .iter()
-                    .all(|&index| image[y + MONSTER_LENGTH - index - 1][x - 1] == SET_PIXEL)
-                    && MONSTER_TOP
-                        .iter()
-                        .all(|&index| image[y + MONSTER_LENGTH - index - 1][x + 1] == SET_PIXEL))))
+                .filter(|edge| find_match(*index, edge.original, tiles).is_some())
+                .count();
+
+            count == 2
+        })
+        .map(|(_, tile)| tile.id)
+        .collect()
 }
 
-fn is_vertical_monster(x: usize, y: usize, image: &[Vec<u8>]) -> bool {
-    // Identifies the monster by checking for the middle of the body
-    // Then it does it checks if for the bottom of the body on both sides (in case it's flipped)
-    // Once it know if it's filled, it checks for the top of the body
+// Positions of every pixel which is part of a sea monster
+fn find_sea_monsters(image: &Grid<u8>) -> anyhow::Result<Vec<usize>> {
+    let windows = find_windows(image);
+    let monster = Grid::parse(&MONSTER, |pixel| pixel == SET_PIXEL)?;
 
-    // forward
-    (MONSTER_MIDDLE
-        .iter()
-        .all(|&index| image[y][x + index] == SET_PIXEL)
-        // original
-        && ((MONSTER_BOTTOM
-            .iter()
-            .all(|&index| image[y + 1][x + index] == SET_PIXEL)
-            && MONSTER_TOP
-                .iter()
-                .all(|&index| image[y - 1][x + index] == SET_PIXEL))
-            // flipped
-            || (MONSTER_BOTTOM
-                .iter()
-                .all(|&index| image[y - 1][x + index] == SET_PIXEL)
-                && MONSTER_TOP
-                    .iter()
-                    .all(|&index| image[y + 1][x + index] == SET_PIXEL))))
-        // backward
-        || (MONSTER_MIDDLE
-            .iter()
-            .all(|&index| image[y][x + MONSTER_LENGTH - index - 1] == SET_PIXEL)
-            // original
-            && ((MONSTER_BOTTOM
+    // Only one orientation shows the monsters, any others are chance matches in the noise
+    let monsters = monster
+        .orientations()
+        .map(|monster| find_monsters(&windows, &monster))
+        .max_by_key(|monsters| monsters.len())
+        .unwrap_or_default();
+
+    Ok(monsters)
+}
+
+// Bits of the 32 pixels starting at every position, so a row of the monster is checked at once
+fn find_windows(image: &Grid<u8>) -> Grid<u32> {
+    let mut windows = Grid::filled(image.width(), image.height(), 0);
+
+    for y in 0..image.height() {
+        let mut window = 0u32;
+
+        for x in (0..image.width()).rev() {
+            window = window << 1 | (image[(x, y)] == SET_PIXEL) as u32;
+            windows[(x, y)] = window;
+        }
+    }
+
+    windows
+}
+
+fn find_monsters(windows: &Grid<u32>, monster: &Grid<bool>) -> Vec<usize> {
+    let mut masks = monster
+        .rows()
+        .map(|row| {
+            row.iter()
+                .rev()
+                .fold(0u32, |mask, set| mask << 1 | *set as u32)
+        })
+        .enumerate()
+        .collect::<Vec<_>>();
+
+    // Checks the busiest rows first, as they rule out most of the positions
+    masks.sort_unstable_by_key(|(_, mask)| std::cmp::Reverse(mask.count_ones()));
+
+    let mut pixels = Vec::new();
+
+    for y in 0..(windows.height() + 1).saturating_sub(monster.height()) {
+        for x in 0..(windows.width() + 1).saturating_sub(monster.width()) {
+            let is_monster = masks
                 .iter()
-                .all(|&index| image[y + 1][x + MONSTER_LENGTH - index - 1] == SET_PIXEL)
-                && MONSTER_TOP
-                    .iter()
-                    .all(|&index| image[y - 1][x + MONSTER_LENGTH - index - 1] == SET_PIXEL))
-                // flipped
-                || (MONSTER_BOTTOM
-                    .iter()
-                    .all(|&index| image[y - 1][x + MONSTER_LENGTH - index - 1] == SET_PIXEL)
-                    && MONSTER_TOP
-                        .iter()
-                        .all(|&index| image[y + 1][x + MONSTER_LENGTH - index - 1] == SET_PIXEL))))
+                .all(|(dy, mask)| windows[(x, y + dy)] & mask == *mask);
+
+            if is_monster {
+                let body = monster.positions().filter(|position| monster[*position]);
+                pixels.extend(body.map(|(dx, dy)| windows.index(x + dx, y + dy)));
+            }
+        }
+    }
+
+    pixels
 }
 
-fn build_image(input: &[&str]) -> anyhow::Result<Vec<Vec<u8>>> {
-    let tiles = parse_tiles(input);
+fn build_image(input: &[u8], lines: &LineIndex) -> anyhow::Result<Grid<u8>> {
+    let tiles = parse_tiles(input, lines)?;
+    let side = (tiles.len() as f64).sqrt() as usize;
+
+    if side * side != tiles.len() {
+        return Err(anyhow!(
+            "Cannot arrange {} tiles into a square image",
+            tiles.len()
+        ));
+    }
 
-    let pixel_count = (tiles.len() as f64).sqrt() as usize * FRAME_SIZE;
-    let mut buffer = vec![Vec::with_capacity(pixel_count); pixel_count];
+    let pixel_count = side * FRAME_SIZE;
+    let mut image = Grid::filled(pixel_count, pixel_count, 0);
 
     for (row, (mut index, mut projection)) in find_left_tiles(&tiles)?.enumerate() {
-        let buffer = &mut buffer[row * FRAME_SIZE..(row + 1) * FRAME_SIZE];
-        projection.draw(buffer);
+        let y = row * FRAME_SIZE;
+        projection.draw(&mut image, 0, y)?;
+
+        for column in 1.. {
+            let Some(r#match) = find_match(index, projection.right_edge(), &tiles) else {
+                break;
+            };
 
-        while let Some(r#match) = find_match(index, projection.right_edge(), &tiles) {
             index = r#match.index;
             let rotation = (3 - r#match.position) % 4;
             projection = Projection::new(rotation, false, !r#match.flipped, &tiles[index]);
-            projection.draw(buffer);
+            projection.draw(&mut image, column * FRAME_SIZE, y)?;
         }
     }
 
-    Ok(buffer)
+    Ok(image)
 }
 
 fn find_left_tiles<'a>(
     tiles: &'a [Tile],
 ) -> anyhow::Result<impl Iterator<Item = (usize, Projection<'a>)>> {
-    let index = find_top_left_tile(tiles).context("Could not find top-left tile")?;
-    let first = (index, Projection::new(0, false, false, &tiles[index]));
+    let (index, rotation) = find_top_left_tile(tiles).context("Could not find top-left tile")?;
+    let first = (
+        index,
+        Projection::new(rotation, false, false, &tiles[index]),
+    );
 
     let iterator = std::iter::successors(Some(first), move |(index, projection)| {
         find_match(*index, projection.bottom_edge(), tiles).map(|r#match| {
@@ -191,14 +220,18 @@ fn find_left_tiles<'a>(
     Ok(iterator)
 }
 
-fn find_top_left_tile(tiles: &[Tile]) -> Option<usize> {
-    tiles
-        .iter()
-        .enumerate()
-        .find_map(|(index, tile)| match find_matches(index, tile, tiles) {
-            [None, Some(_), Some(_), None] => Some(index),
-            _ => None,
-        })
+// Any corner tile works, rotated so that its two unmatched edges end up at the top and left
+fn find_top_left_tile(tiles: &[Tile]) -> Option<(usize, usize)> {
+    tiles.iter().enumerate().find_map(|(index, tile)| {
+        let matches = find_matches(index, tile, tiles).map(|r#match| r#match.is_some());
+
+        (0..4)
+            .find(|edge| {
+                let edges = std::array::from_fn(|i| matches[(edge + i) % 4]);
+                edges == [false, false, true, true]
+            })
+            .map(|edge| (index, 3 - edge))
+    })
 }
 
 fn find_matches(index: usize, tile: &Tile, tiles: &[Tile]) -> [Option<Match>; 4] {
@@ -223,33 +256,42 @@ fn find_match(origin: usize, edge: EdgeLine, tiles: &[Tile]) -> Option<Match> {
         })
 }
 
-fn parse_tiles<'a>(input: &[&'a str]) -> Vec<Tile<'a>> {
-    let mut tiles = Vec::with_capacity((input.len() + 1) / (TILE_SIZE + 2));
-
-    for lines in input.chunks(TILE_SIZE + 2) {
-        tiles.push(Tile::new(lines));
-    }
-
-    tiles
+fn parse_tiles(input: &[u8], lines: &LineIndex) -> anyhow::Result<Vec<Tile>> {
+    parse::sections(lines)
+        .map(|tile| Tile::new(input, tile))
+        .collect()
 }
 
 type EdgeLine = u16;
 
-struct Tile<'a> {
+struct Tile {
     id: usize,
     edges: [Edge; 4],
-    frame: [&'a [u8]; FRAME_SIZE],
+    frame: Grid<u8>,
 }
 
-impl<'a> Tile<'a> {
-    fn new(input: &[&'a str]) -> Self {
-        let id = input[0][5..9].parse().unwrap();
-        let lines = &input[1..=TILE_SIZE];
+impl Tile {
+    fn new(input: &[u8], tile: parse::Section<Range<usize>>) -> anyhow::Result<Self> {
+        let id = parse_id(&input[tile.lines[0].clone()])
+            .with_context(|| format!("Invalid input on line {}", tile.line_number(0)))?;
+
+        let lines = tile.skip(1).lines;
+        let row = |index: usize| &input[lines[index].clone()];
+
+        if lines.len() != TILE_SIZE || lines.iter().any(|line| line.len() != TILE_SIZE) {
+            return Err(anyhow!(
+                "Tile {} on line {} is not {}x{} pixels",
+                id,
+                tile.line_number(0),
+                TILE_SIZE,
+                TILE_SIZE
+            ));
+        }
 
-        let top = lines[0].bytes();
-        let bottom = input[TILE_SIZE].bytes().rev();
-        let left = lines.iter().rev().map(|line| line.as_bytes()[0]);
-        let right = lines.iter().map(|line| *line.as_bytes().last().unwrap());
+        let top = row(0).iter().copied();
+        let bottom = row(TILE_SIZE - 1).iter().rev().copied();
+        let left = (0..TILE_SIZE).rev().map(|index| row(index)[0]);
+        let right = (0..TILE_SIZE).map(|index| row(index)[TILE_SIZE - 1]);
 
         let edges = [
             Edge::new(top),
@@ -258,12 +300,22 @@ impl<'a> Tile<'a> {
             Edge::new(left),
         ];
 
-        let frame = std::array::from_fn(|i| &input[i + 2].as_bytes()[1..FRAME_SIZE + 1]);
+        let rows = (1..=FRAME_SIZE).map(|index| &row(index)[1..=FRAME_SIZE]);
+        let frame = Grid::from_rows(rows, |pixel| pixel)?;
 
-        Tile { id, edges, frame }
+        Ok(Tile { id, edges, frame })
     }
 }
 
+fn parse_id(header: &[u8]) -> anyhow::Result<usize> {
+    let id = header
+        .strip_prefix(b"Tile ")
+        .and_then(|header| header.strip_suffix(b":"))
+        .context("Expected a `Tile <id>:` header")?;
+
+    parse::value(std::str::from_utf8(id)?)
+}
+
 #[derive(Copy, Clone)]
 struct Edge {
     original: EdgeLine,
@@ -305,11 +357,11 @@ struct Projection<'a> {
     rotation: usize,
     flip_x: bool,
     flip_y: bool,
-    tile: &'a Tile<'a>,
+    tile: &'a Tile,
 }
 
 impl<'a> Projection<'a> {
-    fn new(rotation: usize, flip_x: bool, flip_y: bool, tile: &'a Tile<'a>) -> Self {
+    fn new(rotation: usize, flip_x: bool, flip_y: bool, tile: &'a Tile) -> Self {
         Projection {
             rotation,
             flip_x,
@@ -346,32 +398,15 @@ impl<'a> Projection<'a> {
         }
     }
 
-    fn draw(&self, buffer: &mut [Vec<u8>]) {
-        let transmute = self.rotation % 2 == 1;
-        let flip_x = self.flip_x ^ (self.rotation == 1 || self.rotation == 2);
-        let flip_y = self.flip_y ^ (self.rotation == 2 || self.rotation == 3);
-
-        let rows = buffer
-            .iter_mut()
-            .enumerate()
-            .map(|(i, buffer)| (get_frame_index(i, flip_y), buffer));
-
-        for (y, buffer) in rows {
-            for x in (0..FRAME_SIZE).map(|i| get_frame_index(i, flip_x)) {
-                if transmute {
-                    buffer.push(self.tile.frame[x][y]);
-                } else {
-                    buffer.push(self.tile.frame[y][x]);
-                }
-            }
-        }
-    }
-}
+    fn draw(&self, image: &mut Grid<u8>, x: usize, y: usize) -> anyhow::Result<()> {
+        let orientation = Orientation {
+            transpose: self.rotation % 2 == 1,
+            flip_x: self.flip_x ^ (self.rotation == 1 || self.rotation == 2),
+            flip_y: self.flip_y ^ (self.rotation == 2 || self.rotation == 3),
+        };
 
-fn get_frame_index(index: usize, flipped: bool) -> usize {
-    if flipped {
-        FRAME_SIZE - index - 1
-    } else {
-        index
+        image
+            .blit(x, y, &self.tile.frame, orientation)
+            .with_context(|| format!("Tile {} does not fit in the image", self.tile.id))
     }
 }
@@ -1,12 +1,15 @@
+use super::parse;
+use anyhow::{anyhow, Context};
 use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
 
 pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    Ok(solve(input).unknown_ingredients.values().sum::<usize>())
+    Ok(solve(input)?.unknown_ingredients.values().sum::<usize>())
 }
 
 pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let mut known_ingredients = solve(input)
+    let mut known_ingredients = solve(input)?
         .known_ingredients
         .into_iter()
         .collect::<Vec<_>>();
@@ -27,21 +30,49 @@ pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
     Ok(result)
 }
 
+pub fn explain(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
+    let solution = solve(input)?;
+    let mut known_ingredients = solution.known_ingredients.iter().collect::<Vec<_>>();
+    known_ingredients.sort_by_key(|(_, allergen)| **allergen);
+
+    let mut output = String::new();
+
+    for (ingredient, allergen) in known_ingredients {
+        writeln!(output, "{}: {}", allergen, ingredient)?;
+    }
+
+    writeln!(
+        output,
+        "{} ingredients cannot contain any allergen, appearing {} times",
+        solution.unknown_ingredients.len(),
+        solution.unknown_ingredients.values().sum::<usize>()
+    )?;
+
+    Ok(output)
+}
+
 struct Solution<'a> {
     known_ingredients: HashMap<&'a str, &'a str>,
     unknown_ingredients: HashMap<&'a str, usize>,
 }
 
-fn solve<'a>(input: &[&'a str]) -> Solution<'a> {
+fn solve<'a>(input: &[&'a str]) -> anyhow::Result<Solution<'a>> {
     let mut known_ingredients = HashMap::new();
     let mut unknown_ingredients = HashMap::new();
     let mut possible_ingredients = HashMap::<&str, HashSet<&str>>::new();
 
-    for line in input {
-        let (ingredients, allergens) = line.split_once(" (contains ").unwrap();
+    let foods = parse::lines_with(input, |line| {
+        let (ingredients, allergens) = parse::split_once(line, " (contains ")?;
+        let allergens = allergens
+            .strip_suffix(')')
+            .context("Expected a closing `)`")?;
+        Ok((ingredients, allergens))
+    })?;
+
+    for (ingredients, allergens) in foods {
         let ingredients = ingredients.split_ascii_whitespace().collect::<HashSet<_>>();
 
-        for allergen in allergens[..allergens.len() - 1].split(", ") {
+        for allergen in allergens.split(", ") {
             match possible_ingredients.entry(allergen) {
                 Entry::Occupied(mut entry) => {
                     entry
@@ -77,6 +108,13 @@ fn solve<'a>(input: &[&'a str]) -> Solution<'a> {
             }
         }
 
+        if solved.is_empty() {
+            return Err(anyhow!(
+                "Cannot determine which ingredients contain {:?}",
+                possible_ingredients.keys().collect::<Vec<_>>()
+            ));
+        }
+
         for allergen in solved.drain(..) {
             let ingredient = possible_ingredients
                 .remove(allergen)
@@ -90,8 +128,8 @@ fn solve<'a>(input: &[&'a str]) -> Solution<'a> {
         }
     }
 
-    Solution {
+    Ok(Solution {
         known_ingredients,
         unknown_ingredients,
-    }
+    })
 }
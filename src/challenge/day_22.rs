@@ -1,10 +1,18 @@
+use super::parse;
+use anyhow::anyhow;
 use std::cmp::Ordering;
 use std::collections::{HashSet, VecDeque};
 
 pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
     let (mut santa, mut crab) = parse_decks(input)?;
+    let mut previous_decks = HashSet::<(Deck, Deck)>::new();
 
     while !santa.is_empty() && !crab.is_empty() {
+        // Without the rule of the recursive game, a repeated round means nobody ever wins
+        if !previous_decks.insert((santa.clone(), crab.clone())) {
+            return Err(anyhow!("The game never ends"));
+        }
+
         let santa_card = santa.draw_card();
         let crab_card = crab.draw_card();
 
@@ -55,7 +63,9 @@ fn play(mut santa: Deck, mut crab: Deck) -> (bool, Deck) {
             // recursive game is only possible when the number of cards in the deck is at least as
             // many as the card's value.
             // This doesn't apply to crab due to the infinite game prevention rule.
-            if max_santa > max_crab && max_santa > santa_card + crab_card - 2 {
+            if max_santa > max_crab
+                && max_santa as usize > santa_card as usize + crab_card as usize - 2
+            {
                 true
             } else {
                 play(santa.copy(santa_card), crab.copy(crab_card)).0
@@ -79,21 +89,27 @@ fn play(mut santa: Deck, mut crab: Deck) -> (bool, Deck) {
 }
 
 fn parse_decks(input: &[&str]) -> anyhow::Result<(Deck, Deck)> {
-    let index = input.iter().position(|line| line.is_empty()).unwrap();
-    Ok((Deck::new(&input[..index])?, Deck::new(&input[index + 1..])?))
+    let [santa, crab] = parse::split_sections(input)?;
+    let (santa, crab) = (Deck::new(santa)?, Deck::new(crab)?);
+
+    // Rounds cannot end in a draw, and a card of 0 would start a game without any cards
+    let mut cards = HashSet::new();
+
+    for card in santa.0.iter().chain(&crab.0) {
+        if *card == 0 || !cards.insert(*card) {
+            return Err(anyhow!("Card {} is not allowed or appears twice", card));
+        }
+    }
+
+    Ok((santa, crab))
 }
 
 #[derive(Default, Clone, Eq, PartialEq, Hash)]
 struct Deck(VecDeque<u8>);
 
 impl Deck {
-    fn new(cards: &[&str]) -> anyhow::Result<Self> {
-        Ok(Deck(
-            cards[1..]
-                .iter()
-                .map(|card| card.parse())
-                .collect::<Result<_, _>>()?,
-        ))
+    fn new(cards: parse::Section<&str>) -> anyhow::Result<Self> {
+        Ok(Deck(cards.skip(1).lines::<u8>()?.into()))
     }
 
     fn is_empty(&self) -> bool {
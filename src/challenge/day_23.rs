@@ -1,30 +1,44 @@
-pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let mut cups = Cups::new(input[0], 9);
-    cups.simulate(100);
-
-    let mut index = cups.0[cups.find_index(0)].next;
-    let mut result = 0;
+use super::grid::Grid;
+use super::parse;
+use super::simulation::{Cell, Shade, Simulation};
+use anyhow::anyhow;
 
-    loop {
-        let cup = &cups.0[index];
+const LABEL_COUNT: usize = 9;
+const CUP_COUNT: usize = 1000000;
+const MOVES_A: usize = 100;
+const MOVES_B: usize = 10000000;
 
-        if cup.value == 0 {
-            break Ok(result);
-        }
+pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
+    let mut cups = parse::line_with(input, 0, |line| Cups::new(line, LABEL_COUNT))?;
+    cups.simulate(MOVES_A);
 
-        index = cup.next;
-        result = (result * 10) + cup.value + 1;
-    }
+    Ok(cups
+        .labels_after_one()
+        .take(LABEL_COUNT - 1)
+        .fold(0, |result, label| result * 10 + label))
 }
 
 pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let mut cups = Cups::new(input[0], 1000000);
-    cups.simulate(10000000);
+    let mut cups = parse::line_with(input, 0, |line| Cups::new(line, CUP_COUNT))?;
+    cups.simulate(MOVES_B);
 
-    let index = cups.0[cups.find_index(0)].next;
-    let cup = &cups.0[index];
+    Ok(cups.labels_after_one().take(2).product::<usize>())
+}
 
-    Ok((cup.value + 1) * (cups.0[cup.next].value + 1))
+pub fn simulation(input: &[&str], all_cups: bool) -> anyhow::Result<Box<dyn Simulation>> {
+    let (count, moves) = if all_cups {
+        (CUP_COUNT, MOVES_B)
+    } else {
+        (LABEL_COUNT, MOVES_A)
+    };
+
+    Ok(Box::new(CupSimulation {
+        cups: parse::line_with(input, 0, |line| Cups::new(line, count))?,
+        current: 0,
+        moved: None,
+        move_count: 0,
+        moves,
+    }))
 }
 
 struct Cup {
@@ -35,7 +49,17 @@ struct Cup {
 struct Cups(Vec<Cup>);
 
 impl Cups {
-    fn new(input: &str, count: usize) -> Self {
+    fn new(input: &str, count: usize) -> anyhow::Result<Self> {
+        let mut labels = input.as_bytes().to_vec();
+        labels.sort_unstable();
+
+        if !labels.into_iter().eq(b'1'..=b'9') {
+            return Err(anyhow!(
+                "Expected the labels 1 to {} in any order",
+                LABEL_COUNT
+            ));
+        }
+
         let mut cups = Vec::with_capacity(count);
 
         for (i, value) in input.bytes().enumerate() {
@@ -45,7 +69,7 @@ impl Cups {
             });
         }
 
-        for i in 9..count {
+        for i in LABEL_COUNT..count {
             cups.push(Cup {
                 value: i,
                 next: i + 1,
@@ -54,41 +78,119 @@ impl Cups {
 
         cups.last_mut().unwrap().next = 0;
 
-        Cups(cups)
+        Ok(Cups(cups))
     }
 
     fn simulate(&mut self, moves: usize) {
         let mut current = 0;
 
         for _ in 0..moves {
-            let mut removed = [self.0[current].next, 0, 0];
-            removed[1] = self.0[removed[0]].next;
-            removed[2] = self.0[removed[1]].next;
-
-            self.0[current].next = self.0[removed[2]].next;
+            self.play(current);
+            current = self.0[current].next;
+        }
+    }
 
-            let mut destination = (self.0[current].value + self.0.len() - 1) % self.0.len();
+    // Moves the three cups after the current one, returns the indices of the moved cups
+    fn play(&mut self, current: usize) -> [usize; 3] {
+        let mut removed = [self.0[current].next, 0, 0];
+        removed[1] = self.0[removed[0]].next;
+        removed[2] = self.0[removed[1]].next;
 
-            while removed
-                .iter()
-                .any(|index| self.0[*index].value == destination)
-            {
-                destination = (destination + self.0.len() - 1) % self.0.len();
-            }
+        self.0[current].next = self.0[removed[2]].next;
 
-            let destination = self.find_index(destination);
-            self.0[removed[2]].next = self.0[destination].next;
-            self.0[destination].next = removed[0];
+        let mut destination = (self.0[current].value + self.0.len() - 1) % self.0.len();
 
-            current = self.0[current].next;
+        while removed
+            .iter()
+            .any(|index| self.0[*index].value == destination)
+        {
+            destination = (destination + self.0.len() - 1) % self.0.len();
         }
+
+        let destination = self.find_index(destination);
+        self.0[removed[2]].next = self.0[destination].next;
+        self.0[destination].next = removed[0];
+
+        removed
+    }
+
+    // Labels of the cups clockwise from cup 1, the labels start at 1 where the values start at 0
+    fn labels_after_one(&self) -> impl Iterator<Item = usize> + '_ {
+        let first = self.0[self.find_index(0)].next;
+
+        std::iter::successors(Some(first), |index| Some(self.0[*index].next))
+            .map(|index| self.0[index].value)
+            .take_while(|value| *value != 0)
+            .map(|value| value + 1)
     }
 
     fn find_index(&self, value: usize) -> usize {
-        if value < 9 {
+        if value < LABEL_COUNT {
             self.0.iter().position(|cup| cup.value == value).unwrap()
         } else {
             value
         }
     }
 }
+
+// Only shows as many cups as fit on a line, starting at the current cup
+const VISIBLE_CUPS: usize = 20;
+
+struct CupSimulation {
+    cups: Cups,
+    current: usize,
+    moved: Option<[usize; 3]>,
+    move_count: usize,
+    moves: usize,
+}
+
+impl Simulation for CupSimulation {
+    fn step(&mut self) -> bool {
+        if self.move_count == self.moves {
+            return false;
+        }
+
+        self.moved = Some(self.cups.play(self.current));
+        self.current = self.cups.0[self.current].next;
+        self.move_count += 1;
+        true
+    }
+
+    fn render(&self, _: &[isize]) -> Grid<Cell> {
+        let mut cells = Vec::new();
+        let mut index = self.current;
+
+        for _ in 0..VISIBLE_CUPS.min(self.cups.0.len()) {
+            let shade = if index == self.current {
+                Shade::Highlight
+            } else if self.moved.is_some_and(|moved| moved.contains(&index)) {
+                Shade::Activated
+            } else {
+                Shade::Active
+            };
+
+            let label = (self.cups.0[index].value + 1).to_string();
+            cells.extend(label.chars().map(|symbol| Cell::new(symbol, shade)));
+            cells.push(Cell::BLANK);
+            index = self.cups.0[index].next;
+        }
+
+        Grid::new(cells.len(), 1, cells)
+    }
+
+    fn status(&self) -> String {
+        let labels = self
+            .cups
+            .labels_after_one()
+            .take(LABEL_COUNT - 1)
+            .map(|label| label.to_string())
+            .collect::<Vec<_>>();
+
+        format!(
+            "move {} of {}, cups after 1: {}",
+            self.move_count,
+            self.moves,
+            labels.join(" ")
+        )
+    }
+}
@@ -1,29 +1,54 @@
+use super::grid::Grid;
+use super::parse;
+use super::simulation::{self, Cell, Simulation};
+use anyhow::{anyhow, Context};
 use std::slice::Windows;
 
+const DAYS: usize = 100;
+
 pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    Ok(BlackTiles::new(&parse_position(input)).count())
+    Ok(BlackTiles::new(&parse_position(input)?).count())
 }
 
 pub fn part_b(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let positions = parse_position(input);
-    let black_tiles = BlackTiles::new(&positions).map(normalize);
-    let mut lobby = Lobby::new(150, 200, black_tiles);
+    let mut lobby = build_lobby(input)?;
 
-    for _ in 0..99 {
+    for _ in 0..DAYS - 1 {
         lobby.update();
     }
 
     Ok(lobby.update())
 }
 
+pub fn simulation(input: &[&str]) -> anyhow::Result<Box<dyn Simulation>> {
+    let lobby = build_lobby(input)?;
+
+    Ok(Box::new(LobbySimulation {
+        count: lobby.tiles.cells().iter().filter(|black| **black).count(),
+        previous: lobby.tiles.clone(),
+        lobby,
+        day: 0,
+    }))
+}
+
+fn build_lobby(input: &[&str]) -> anyhow::Result<Lobby> {
+    let positions = parse_position(input)?;
+    let black_tiles = BlackTiles::new(&positions).map(normalize);
+    Lobby::new(150, 200, black_tiles)
+}
+
 struct Lobby {
-    width: usize,
-    tiles: Vec<bool>,
+    tiles: Grid<bool>,
+    neighbors: [[isize; 6]; 2],
     updated: Vec<usize>,
 }
 
 impl Lobby {
-    fn new(width: usize, height: usize, black_tiles: impl Iterator<Item = (i8, i8)>) -> Self {
+    fn new(
+        width: usize,
+        height: usize,
+        black_tiles: impl Iterator<Item = (i8, i8)>,
+    ) -> anyhow::Result<Self> {
         let mut dx = (width / 2) as isize;
         let dy = (height / 2) as isize;
 
@@ -31,27 +56,36 @@ impl Lobby {
             dx += 1;
         }
 
-        let mut tiles = vec![false; width * height];
+        let mut tiles = Grid::filled(width, height, false);
 
         for (x, y) in black_tiles {
-            tiles[(x as isize + dx + (y as isize + dy) * width as isize) as usize] = true;
+            let tile = usize::try_from(x as isize + dx)
+                .ok()
+                .zip(usize::try_from(y as isize + dy).ok())
+                .and_then(|(x, y)| tiles.get_mut(x, y))
+                .with_context(|| format!("Tile {:?} is outside of the lobby", (x, y)))?;
+
+            *tile = true;
         }
 
-        Lobby {
-            width,
+        Ok(Lobby {
+            neighbors: tiles.hex_offsets(),
             tiles,
             updated: Vec::new(),
-        }
+        })
     }
 
     fn update(&mut self) -> usize {
         let mut count = 0;
+        let width = self.tiles.width();
+        let tiles = self.tiles.cells_mut();
 
         // skips the first and last row + 1 tile
-        for index in self.width + 1..self.tiles.len() - self.width - 1 {
-            let black_neighbors = self.count_black_neighbors(index);
+        for index in width + 1..tiles.len() - width - 1 {
+            let neighbors = &self.neighbors[(index / width) % 2];
+            let black_neighbors = count_black_neighbors(tiles, neighbors, index);
 
-            if self.tiles[index] {
+            if tiles[index] {
                 if black_neighbors == 0 || black_neighbors > 2 {
                     self.updated.push(index);
                 } else {
@@ -64,42 +98,29 @@ impl Lobby {
         }
 
         for index in self.updated.drain(..) {
-            self.tiles[index] = !self.tiles[index];
+            tiles[index] = !tiles[index];
         }
 
         count
     }
+}
 
-    fn count_black_neighbors(&self, index: usize) -> usize {
-        let neighbors = if (index / self.width) % 2 == 0 {
-            [
-                index + 1,
-                index - 1,
-                index + self.width,
-                index + self.width - 1,
-                index - self.width,
-                index - self.width - 1,
-            ]
-        } else {
-            [
-                index + 1,
-                index - 1,
-                index + self.width,
-                index + self.width + 1,
-                index - self.width,
-                index - self.width + 1,
-            ]
-        };
-
-        neighbors.iter().filter(|index| self.tiles[**index]).count()
-    }
+// Neighbors by their index, so the first and last tile of a row wrap around to the other side of
+// the lobby
+fn count_black_neighbors(tiles: &[bool], neighbors: &[isize; 6], index: usize) -> usize {
+    neighbors
+        .iter()
+        .filter(|offset| tiles[index.wrapping_add_signed(**offset)])
+        .count()
 }
 
 struct BlackTiles<'a>(usize, Windows<'a, (i8, i8)>, (i8, i8));
 
 impl<'a> BlackTiles<'a> {
     fn new(positions: &'a [(i8, i8)]) -> Self {
-        BlackTiles(1, positions.windows(2), *positions.last().unwrap())
+        // Without any positions, there is no last tile to flip either
+        let last = positions.last().copied().unwrap_or_default();
+        BlackTiles(positions.len().min(1), positions.windows(2), last)
     }
 }
 
@@ -135,10 +156,59 @@ impl Iterator for BlackTiles<'_> {
     }
 }
 
-fn parse_position(input: &[&str]) -> Vec<(i8, i8)> {
-    let mut positions = input.iter().map(|line| locate(line)).collect::<Vec<_>>();
+fn parse_position(input: &[&str]) -> anyhow::Result<Vec<(i8, i8)>> {
+    let mut positions = parse::lines_with(input, locate)?;
     positions.sort();
-    positions
+    Ok(positions)
+}
+
+struct LobbySimulation {
+    lobby: Lobby,
+    previous: Grid<bool>,
+    count: usize,
+    day: usize,
+}
+
+impl Simulation for LobbySimulation {
+    fn step(&mut self) -> bool {
+        if self.day == DAYS {
+            return false;
+        }
+
+        self.previous.clone_from(&self.lobby.tiles);
+        self.count = self.lobby.update();
+        self.day += 1;
+        true
+    }
+
+    // Every odd row is shifted by half a tile, so every tile takes two columns
+    fn render(&self, _: &[isize]) -> Grid<Cell> {
+        let tiles = &self.lobby.tiles;
+        let width = tiles.width() * 2 + 1;
+        let mut cells = Vec::with_capacity(width * tiles.height());
+
+        for (y, row) in tiles.rows().enumerate() {
+            let start = cells.len();
+
+            if y % 2 == 1 {
+                cells.push(Cell::BLANK);
+            }
+
+            for (x, black) in row.iter().enumerate() {
+                let symbol = if *black { '#' } else { '.' };
+                let shade = simulation::shade(*black, self.previous[(x, y)]);
+                cells.extend([Cell::new(symbol, shade), Cell::BLANK]);
+            }
+
+            cells.resize(start + width, Cell::BLANK);
+        }
+
+        Grid::new(width, tiles.height(), cells)
+    }
+
+    fn status(&self) -> String {
+        format!("day {} of {}, {} black tiles", self.day, DAYS, self.count)
+    }
 }
 
 fn normalize(position: (i8, i8)) -> (i8, i8) {
@@ -151,37 +221,29 @@ fn normalize(position: (i8, i8)) -> (i8, i8) {
     (x, position.1)
 }
 
-fn locate(input: &str) -> (i8, i8) {
-    let input = input.as_bytes();
-    let (mut x, mut y) = (0, 0);
-    let mut i = 0;
-
-    while i < input.len() {
-        let char = input[i];
-        i += 1;
-
-        match char {
-            b'e' => {
-                x -= 2;
-                continue;
-            }
-            b'w' => {
-                x += 2;
-                continue;
+fn locate(input: &str) -> anyhow::Result<(i8, i8)> {
+    let mut input = input.bytes();
+    let (mut x, mut y) = (0i8, 0i8);
+
+    while let Some(char) = input.next() {
+        let (dx, dy) = match char {
+            b'e' => (-2, 0),
+            b'w' => (2, 0),
+            b's' | b'n' => {
+                let dy = if char == b's' { -1 } else { 1 };
+
+                match input.next() {
+                    Some(b'e') => (-1, dy),
+                    Some(b'w') => (1, dy),
+                    _ => return Err(anyhow!("Expected `e` or `w` after `{}`", char as char)),
+                }
             }
-            b's' => y -= 1,
-            b'n' => y += 1,
-            _ => unreachable!(),
-        }
-
-        match input[i] {
-            b'e' => x -= 1,
-            b'w' => x += 1,
-            _ => unreachable!(),
-        }
+            _ => return Err(anyhow!("Unknown direction {:?}", char as char)),
+        };
 
-        i += 1;
+        x = x.checked_add(dx).context("Tile is too far away")?;
+        y = y.checked_add(dy).context("Tile is too far away")?;
     }
 
-    (x, y)
+    Ok((x, y))
 }
@@ -1,10 +1,14 @@
+use super::parse;
+use anyhow::{anyhow, Context};
+
 const M: usize = 20201227;
 
 pub fn part_a(input: &[&str]) -> anyhow::Result<impl std::fmt::Display> {
-    let card_key = input[0].parse::<usize>().unwrap();
-    let door_key = input[1].parse::<usize>().unwrap();
+    let card_key = parse::line_with(input, 0, parse_key)?;
+    let door_key = parse::line_with(input, 1, parse_key)?;
 
-    let (public_key, private_key) = brute_force_key_pair(card_key, door_key);
+    let (public_key, private_key) =
+        brute_force_key_pair(card_key, door_key).context("Cannot find a private key")?;
 
     let subject = if public_key == card_key {
         door_key
@@ -19,16 +23,27 @@ fn encrypt(subject: usize, private_key: usize) -> usize {
     (0..private_key).fold(1, |key, _| (key * subject) % M)
 }
 
-fn brute_force_key_pair(card_key: usize, door_key: usize) -> (usize, usize) {
+fn brute_force_key_pair(card_key: usize, door_key: usize) -> Option<(usize, usize)> {
     let mut public_key = 1;
-    let mut private_key = 0;
 
-    loop {
+    // The public keys repeat after at most M - 1 loops
+    for private_key in 1..M {
         public_key = (public_key * 7) % M;
-        private_key += 1;
 
         if public_key == card_key || public_key == door_key {
-            break (public_key, private_key);
+            return Some((public_key, private_key));
         }
     }
+
+    None
+}
+
+fn parse_key(line: &str) -> anyhow::Result<usize> {
+    let key = parse::value(line)?;
+
+    if key == 0 || key >= M {
+        return Err(anyhow!("Public key {} is outside of 1 to {}", key, M - 1));
+    }
+
+    Ok(key)
 }
@@ -0,0 +1,26 @@
+use super::{Challenge, Day};
+
+// What can go wrong in the challenge framework, as opposed to within a day's own parsing
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("{0} is not a valid challenge, expecting \\d+[AB]")]
+    InvalidChallenge(String),
+    #[error("{0} is not a valid day, expecting 1 to 25")]
+    InvalidDay(String),
+    #[error("{0} is not a valid part, expecting A or B")]
+    InvalidPart(String),
+    #[error("Cannot find solution for {0}")]
+    UnknownChallenge(Challenge),
+    #[error("Cannot fetch the input of {day}")]
+    InputFetch {
+        day: Day,
+        #[source]
+        source: anyhow::Error,
+    },
+    #[error("{challenge} failed")]
+    Solver {
+        challenge: Challenge,
+        #[source]
+        source: anyhow::Error,
+    },
+}
@@ -0,0 +1,401 @@
+use anyhow::{anyhow, Context};
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+use std::slice::ChunksExact;
+
+pub type Direction = (isize, isize);
+
+pub const ORTHOGONAL: [Direction; 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+pub const ADJACENT: [Direction; 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+// Hexagons in offset coordinates, where every odd row is shifted half a cell to the right
+const HEX_EVEN_ROW: [Direction; 6] = [(1, 0), (-1, 0), (0, 1), (-1, 1), (0, -1), (-1, -1)];
+const HEX_ODD_ROW: [Direction; 6] = [(1, 0), (-1, 0), (0, 1), (1, 1), (0, -1), (1, -1)];
+
+// How a grid is laid down when drawn into another one, with the flips applied before transposing
+#[derive(Copy, Clone, Default)]
+pub struct Orientation {
+    pub transpose: bool,
+    pub flip_x: bool,
+    pub flip_y: bool,
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "Grid size does not match");
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn parse(input: &[&str], parse: impl FnMut(u8) -> T) -> anyhow::Result<Self> {
+        Self::from_rows(input.iter().map(|line| line.as_bytes()), parse)
+    }
+
+    pub fn from_rows<'a>(
+        rows: impl IntoIterator<Item = &'a [u8]>,
+        mut parse: impl FnMut(u8) -> T,
+    ) -> anyhow::Result<Self> {
+        let rows = rows.into_iter();
+        let capacity = rows.size_hint().0;
+
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for row in rows {
+            let width = *width.get_or_insert_with(|| {
+                cells.reserve(row.len() * capacity);
+                row.len()
+            });
+
+            if row.len() != width {
+                return Err(anyhow!(
+                    "Row {} has {} cells, expected {}",
+                    height + 1,
+                    row.len(),
+                    width
+                ));
+            }
+
+            cells.extend(row.iter().map(|byte| parse(*byte)));
+            height += 1;
+        }
+
+        let width = width.context("Cannot parse a grid without any rows")?;
+        Ok(Grid::new(width, height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    pub fn rows(&self) -> ChunksExact<'_, T> {
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    pub fn index(&self, x: usize, y: usize) -> usize {
+        x + y * self.width
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[x + y * self.width])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[x + y * self.width])
+        } else {
+            None
+        }
+    }
+
+    pub fn offset(&self, x: usize, y: usize, (dx, dy): Direction) -> Option<(usize, usize)> {
+        // Stepping past zero wraps around to a value which is out of bounds as well
+        let x = x.wrapping_add_signed(dx);
+        let y = y.wrapping_add_signed(dy);
+
+        if x < self.width && y < self.height {
+            Some((x, y))
+        } else {
+            None
+        }
+    }
+
+    pub fn neighbors<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        directions
+            .iter()
+            .filter_map(move |direction| self.offset(x, y, *direction))
+    }
+
+    // Index offsets of the given directions, only valid for positions which are not on the boundary
+    pub fn neighbor_offsets<const N: usize>(&self, directions: [Direction; N]) -> [isize; N] {
+        directions.map(|(dx, dy)| dx + dy * self.width as isize)
+    }
+
+    pub fn orthogonal_offsets(&self) -> [isize; 4] {
+        self.neighbor_offsets(ORTHOGONAL)
+    }
+
+    // Index offsets of the hexagons around a cell in an even and in an odd row
+    pub fn hex_offsets(&self) -> [[isize; 6]; 2] {
+        [
+            self.neighbor_offsets(HEX_EVEN_ROW),
+            self.neighbor_offsets(HEX_ODD_ROW),
+        ]
+    }
+
+    // Every position from (x, y) in the given direction until the edge of the grid
+    pub fn ray(
+        &self,
+        x: usize,
+        y: usize,
+        direction: Direction,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        std::iter::successors(self.offset(x, y, direction), move |(x, y)| {
+            self.offset(*x, *y, direction)
+        })
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut cells = Vec::with_capacity(self.cells.len());
+
+        for x in 0..self.width {
+            cells.extend(self.cells[x..].iter().step_by(self.width).cloned());
+        }
+
+        Grid::new(self.height, self.width, cells)
+    }
+
+    pub fn flip_x(&self) -> Self {
+        let mut cells = Vec::with_capacity(self.cells.len());
+
+        for row in self.rows() {
+            cells.extend(row.iter().rev().cloned());
+        }
+
+        Grid::new(self.width, self.height, cells)
+    }
+
+    pub fn flip_y(&self) -> Self {
+        let mut cells = Vec::with_capacity(self.cells.len());
+
+        for row in self.rows().rev() {
+            cells.extend_from_slice(row);
+        }
+
+        Grid::new(self.width, self.height, cells)
+    }
+
+    // Rotates clockwise by a quarter turn
+    pub fn rotate(&self) -> Self {
+        self.transpose().flip_x()
+    }
+
+    // All eight rotations and reflections, starting with the grid itself
+    pub fn orientations(&self) -> impl Iterator<Item = Self> {
+        std::iter::successors(Some(self.clone()), |grid| Some(grid.rotate()))
+            .take(4)
+            .flat_map(|grid| {
+                let flipped = grid.flip_x();
+                [grid, flipped]
+            })
+    }
+
+    // Draws the other grid with its top-left corner at (x, y), laid down in the given orientation
+    pub fn blit(
+        &mut self,
+        x: usize,
+        y: usize,
+        other: &Grid<T>,
+        orientation: Orientation,
+    ) -> anyhow::Result<()> {
+        let (width, height) = if orientation.transpose {
+            (other.height, other.width)
+        } else {
+            (other.width, other.height)
+        };
+
+        if x + width > self.width || y + height > self.height {
+            return Err(anyhow!(
+                "A {}x{} grid at {:?} falls outside of the {}x{} grid",
+                width,
+                height,
+                (x, y),
+                self.width,
+                self.height
+            ));
+        }
+
+        for dy in 0..height {
+            let start = self.index(x, y + dy);
+            let source_y = if orientation.flip_y {
+                height - dy - 1
+            } else {
+                dy
+            };
+
+            for (dx, cell) in self.cells[start..start + width].iter_mut().enumerate() {
+                let source_x = if orientation.flip_x {
+                    width - dx - 1
+                } else {
+                    dx
+                };
+
+                let index = if orientation.transpose {
+                    other.index(source_y, source_x)
+                } else {
+                    other.index(source_x, source_y)
+                };
+
+                cell.clone_from(&other.cells[index]);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        self.get(x, y).expect("Position is outside of the grid")
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        self.get_mut(x, y).expect("Position is outside of the grid")
+    }
+}
+
+pub trait Symbol {
+    fn symbol(&self) -> char;
+}
+
+impl Symbol for u8 {
+    fn symbol(&self) -> char {
+        *self as char
+    }
+}
+
+impl Symbol for bool {
+    fn symbol(&self) -> char {
+        if *self {
+            '#'
+        } else {
+            '.'
+        }
+    }
+}
+
+impl<T: Symbol> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell.symbol())?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Flattened index space of an N-dimensional box, for the automatons which outgrow a plane
+pub struct Space<const D: usize> {
+    sizes: [usize; D],
+    strides: [usize; D],
+}
+
+impl<const D: usize> Space<D> {
+    pub fn new(sizes: [usize; D]) -> Self {
+        let mut strides = [1; D];
+
+        for i in 1..D {
+            strides[i] = strides[i - 1] * sizes[i - 1];
+        }
+
+        Space { sizes, strides }
+    }
+
+    pub fn len(&self) -> usize {
+        self.sizes.iter().product()
+    }
+
+    pub fn sizes(&self) -> [usize; D] {
+        self.sizes
+    }
+
+    pub fn index(&self, point: [usize; D]) -> usize {
+        point
+            .iter()
+            .zip(&self.strides)
+            .map(|(value, stride)| value * stride)
+            .sum()
+    }
+
+    pub fn point(&self, index: usize) -> [usize; D] {
+        std::array::from_fn(|i| index / self.strides[i] % self.sizes[i])
+    }
+
+    // Index offsets of the 3^D - 1 neighbors, only valid for points which are not on the boundary
+    pub fn neighbor_offsets(&self) -> Vec<isize> {
+        let mut offsets = vec![0isize];
+
+        for stride in self.strides {
+            let stride = stride as isize;
+
+            offsets = offsets
+                .iter()
+                .flat_map(|offset| [offset - stride, *offset, offset + stride])
+                .collect();
+        }
+
+        offsets.retain(|offset| *offset != 0);
+        offsets
+    }
+
+    pub fn interior(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len()).filter(|index| {
+            self.point(*index)
+                .iter()
+                .zip(&self.sizes)
+                .all(|(value, size)| *value > 0 && *value + 1 < *size)
+        })
+    }
+}
@@ -0,0 +1,161 @@
+use std::ops::ControlFlow;
+
+// Finds k values among numbers which add up to a target. Values are told apart by their position,
+// so a value can only be used as often as it occurs.
+#[derive(Copy, Clone, Debug)]
+pub struct KSum {
+    pub k: usize,
+    pub target: u64,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Combination {
+    // Positions in the numbers, in increasing order, along with the values at them
+    pub indices: Vec<usize>,
+    pub values: Vec<u64>,
+}
+
+impl Combination {
+    // None when the product does not fit, which large targets allow
+    pub fn product(&self) -> Option<u64> {
+        self.values
+            .iter()
+            .try_fold(1u64, |product, &value| product.checked_mul(value))
+    }
+}
+
+impl KSum {
+    pub fn new(k: usize, target: u64) -> Self {
+        KSum { k, target }
+    }
+
+    pub fn first(&self, numbers: &[u64]) -> Option<Combination> {
+        let mut first = None;
+
+        self.search(numbers, |combination| {
+            first = Some(combination);
+            ControlFlow::Break(())
+        });
+
+        first
+    }
+
+    // Every combination in order of their smallest values
+    pub fn all(&self, numbers: &[u64]) -> Vec<Combination> {
+        let mut all = Vec::new();
+
+        self.search(numbers, |combination| {
+            all.push(combination);
+            ControlFlow::Continue(())
+        });
+
+        all
+    }
+
+    fn search(&self, numbers: &[u64], mut found: impl FnMut(Combination) -> ControlFlow<()>) {
+        let mut sorted = numbers.iter().copied().zip(0..).collect::<Vec<_>>();
+        sorted.sort_unstable();
+
+        let mut emit = |chosen: &[(u64, usize)]| {
+            let mut chosen = chosen.to_vec();
+            chosen.sort_unstable_by_key(|&(_, index)| index);
+
+            found(Combination {
+                indices: chosen.iter().map(|&(_, index)| index).collect(),
+                values: chosen.iter().map(|&(value, _)| value).collect(),
+            })
+        };
+
+        let _ = search(
+            &sorted,
+            self.k,
+            self.target,
+            &mut Vec::with_capacity(self.k),
+            &mut emit,
+        );
+    }
+}
+
+// Fixes the smallest value of the combination and looks for the other k - 1 values after it,
+// until only a pair is left, which two pointers find from both ends of the sorted values
+fn search(
+    sorted: &[(u64, usize)],
+    k: usize,
+    target: u64,
+    chosen: &mut Vec<(u64, usize)>,
+    emit: &mut impl FnMut(&[(u64, usize)]) -> ControlFlow<()>,
+) -> ControlFlow<()> {
+    match k {
+        0 if target == 0 => emit(chosen),
+        0 => ControlFlow::Continue(()),
+        1 => {
+            let start = sorted.partition_point(|&(value, _)| value < target);
+
+            for &number in sorted[start..]
+                .iter()
+                .take_while(|&&(value, _)| value == target)
+            {
+                chosen.push(number);
+                let flow = emit(chosen);
+                chosen.pop();
+                flow?;
+            }
+
+            ControlFlow::Continue(())
+        }
+        2 => pairs(sorted, target, chosen, emit),
+        _ => {
+            for (i, &number) in sorted.iter().enumerate() {
+                // Every later value is at least as large, so the remaining values add up to more
+                match (number.0.checked_mul(k as u64), target.checked_sub(number.0)) {
+                    (Some(minimum), Some(rest)) if minimum <= target => {
+                        chosen.push(number);
+                        let flow = search(&sorted[i + 1..], k - 1, rest, chosen, emit);
+                        chosen.pop();
+                        flow?;
+                    }
+                    _ => break,
+                }
+            }
+
+            ControlFlow::Continue(())
+        }
+    }
+}
+
+fn pairs(
+    sorted: &[(u64, usize)],
+    target: u64,
+    chosen: &mut Vec<(u64, usize)>,
+    emit: &mut impl FnMut(&[(u64, usize)]) -> ControlFlow<()>,
+) -> ControlFlow<()> {
+    let (mut low, mut high) = (0, sorted.len());
+
+    while high > low + 1 {
+        let sum = sorted[low].0.saturating_add(sorted[high - 1].0);
+
+        if sum < target {
+            low += 1;
+        } else if sum > target {
+            high -= 1;
+        } else {
+            // Equal values form runs, any value of the low run pairs with any of the high run
+            let low_end = low + sorted[low..high].partition_point(|n| n.0 == sorted[low].0);
+            let high_start = low + sorted[low..high].partition_point(|n| n.0 < sorted[high - 1].0);
+
+            for i in low..low_end {
+                // When both runs are the same, values only pair with the ones after them
+                for j in high_start.max(i + 1)..high {
+                    chosen.extend([sorted[i], sorted[j]]);
+                    let flow = emit(chosen);
+                    chosen.truncate(chosen.len() - 2);
+                    flow?;
+                }
+            }
+
+            (low, high) = (low_end, high_start);
+        }
+    }
+
+    ControlFlow::Continue(())
+}
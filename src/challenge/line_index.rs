@@ -0,0 +1,43 @@
+use std::ops::{Deref, Range};
+
+// Byte ranges of every line in the input, following the same rules as `str::lines`. Lets the
+// solutions work on the raw input without collecting (and validating) a `&str` per line.
+pub struct LineIndex(Vec<Range<usize>>);
+
+impl LineIndex {
+    pub fn new(input: &[u8]) -> Self {
+        let mut lines = Vec::with_capacity(input.len() / 16);
+        let mut start = 0;
+
+        for (i, byte) in input.iter().enumerate() {
+            if *byte == b'\n' {
+                let end = if i > start && input[i - 1] == b'\r' {
+                    i - 1
+                } else {
+                    i
+                };
+
+                lines.push(start..end);
+                start = i + 1;
+            }
+        }
+
+        if start < input.len() {
+            lines.push(start..input.len());
+        }
+
+        LineIndex(lines)
+    }
+
+    pub fn lines<'a>(&'a self, input: &'a [u8]) -> impl DoubleEndedIterator<Item = &'a [u8]> {
+        self.0.iter().map(|range| &input[range.clone()])
+    }
+}
+
+impl Deref for LineIndex {
+    type Target = [Range<usize>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
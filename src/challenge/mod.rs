@@ -4,7 +4,7 @@ use num_enum::{IntoPrimitive, TryFromPrimitive};
 use regex::Regex;
 use std::collections::HashMap;
 use std::str::FromStr;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 mod day_01;
 mod day_02;
@@ -31,6 +31,22 @@ mod day_22;
 mod day_23;
 mod day_24;
 mod day_25;
+mod error;
+mod grid;
+mod k_sum;
+mod line_index;
+mod parse;
+mod simulation;
+
+pub use day_02::Parsing;
+pub use day_03::{Slope, TobogganMap};
+pub use day_04::{PassportSchema, ReportFormat};
+pub use day_05::{Plane, Seat};
+pub use error::Error;
+pub use grid::Grid;
+pub use k_sum::{Combination, KSum};
+pub use line_index::LineIndex;
+pub use simulation::{every_slice, Cell, Shade, Simulation};
 
 lazy_static! {
     static ref CHALLENGE_PATTERN: Regex =
@@ -93,6 +109,25 @@ lazy_static! {
 
         solutions
     };
+    static ref EXPLANATIONS: Explanations = {
+        use Day::*;
+        use Part::*;
+
+        let mut explanations = Explanations::new();
+
+        explanations.add(Day01, PartA, day_01::explain_a);
+        explanations.add(Day01, PartB, day_01::explain_b);
+        explanations.add(Day09, PartA, day_09::explain);
+        explanations.add(Day09, PartB, day_09::explain);
+        explanations.add(Day16, PartA, day_16::explain);
+        explanations.add(Day16, PartB, day_16::explain);
+        explanations.add(Day20, PartA, day_20::explain);
+        explanations.add(Day20, PartB, day_20::explain);
+        explanations.add(Day21, PartA, day_21::explain);
+        explanations.add(Day21, PartB, day_21::explain);
+
+        explanations
+    };
 }
 
 #[derive(
@@ -128,6 +163,10 @@ pub enum Day {
 }
 
 impl Day {
+    pub fn all() -> impl Iterator<Item = Day> {
+        (1..=25).map(|index| Day::try_from(index).unwrap())
+    }
+
     fn name(&self) -> &'static str {
         use Day::*;
 
@@ -160,9 +199,12 @@ impl Day {
         }
     }
 
-    async fn input(&self) -> anyhow::Result<String> {
+    #[tracing::instrument(skip(self), fields(day = u8::from(*self)))]
+    async fn input(&self) -> Result<String, Error> {
         let index = u8::from(*self);
-        crate::http::get(format!("https://adventofcode.com/2020/day/{}/input", index)).await
+        crate::http::get(format!("https://adventofcode.com/2020/day/{}/input", index))
+            .await
+            .map_err(|source| Error::InputFetch { day: *self, source })
     }
 }
 
@@ -173,14 +215,14 @@ impl std::fmt::Display for Day {
 }
 
 impl FromStr for Day {
-    type Err = anyhow::Error;
+    type Err = Error;
 
     fn from_str(string: &str) -> Result<Self, Self::Err> {
-        let index = string.parse::<u8>()?;
-
-        index
-            .try_into()
-            .with_context(|| format!("Day {} is out of range", index))
+        string
+            .parse::<u8>()
+            .ok()
+            .and_then(|index| index.try_into().ok())
+            .ok_or_else(|| Error::InvalidDay(string.to_string()))
     }
 }
 
@@ -190,6 +232,15 @@ pub enum Part {
     PartB,
 }
 
+impl Part {
+    pub fn letter(&self) -> char {
+        match self {
+            Part::PartA => 'a',
+            Part::PartB => 'b',
+        }
+    }
+}
+
 impl std::fmt::Display for Part {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -200,13 +251,13 @@ impl std::fmt::Display for Part {
 }
 
 impl FromStr for Part {
-    type Err = anyhow::Error;
+    type Err = Error;
 
     fn from_str(string: &str) -> Result<Self, Self::Err> {
         match string {
             "a" | "A" => Ok(Part::PartA),
             "b" | "B" => Ok(Part::PartB),
-            _ => Err(anyhow!("{} is not a valid part, expecting A or B", string)),
+            _ => Err(Error::InvalidPart(string.to_string())),
         }
     }
 }
@@ -218,29 +269,30 @@ impl Challenge {
     pub fn new(day: Day, part: Part) -> Self {
         Challenge(day, part)
     }
+
+    pub fn day(&self) -> Day {
+        self.0
+    }
+
+    pub fn part(&self) -> Part {
+        self.1
+    }
+
+    // Short form which can be parsed back, e.g. 05a
+    pub fn code(&self) -> String {
+        format!("{:02}{}", u8::from(self.0), self.1.letter())
+    }
 }
 
 impl FromStr for Challenge {
-    type Err = anyhow::Error;
+    type Err = Error;
 
     fn from_str(string: &str) -> Result<Self, Self::Err> {
         let captures = CHALLENGE_PATTERN
             .captures(string)
-            .with_context(|| format!("{} is not a valid challenge, expecting \\d+[AB]", string))?;
+            .ok_or_else(|| Error::InvalidChallenge(string.to_string()))?;
 
-        let day = captures
-            .get(1)
-            .context("Day capture group is missing")?
-            .as_str()
-            .parse()?;
-
-        let part = captures
-            .get(2)
-            .context("Part capture group is missing")?
-            .as_str()
-            .parse()?;
-
-        Ok(Challenge(day, part))
+        Ok(Challenge(captures[1].parse()?, captures[2].parse()?))
     }
 }
 
@@ -250,24 +302,147 @@ impl std::fmt::Display for Challenge {
     }
 }
 
-pub async fn solve(challenge: &Challenge) -> anyhow::Result<()> {
-    SOLUTIONS.solve(challenge).await
+pub async fn solve(challenge: &Challenge, explain: bool) -> anyhow::Result<()> {
+    SOLUTIONS.solve(challenge, explain).await
+}
+
+// Every challenge with a registered solution, in order
+pub fn challenges() -> impl Iterator<Item = Challenge> {
+    let mut challenges = SOLUTIONS.0.keys().copied().collect::<Vec<_>>();
+    challenges.sort();
+    challenges.into_iter()
+}
+
+pub fn run(challenge: &Challenge, input: &str) -> Result<Answer, Error> {
+    SOLUTIONS.run(challenge, input)
+}
+
+// The input from a file when one is given (e.g. written by `generate`), or else the puzzle input
+pub async fn read_input(day: Day, path: Option<&str>) -> anyhow::Result<String> {
+    match path {
+        Some(path) => {
+            std::fs::read_to_string(path).with_context(|| format!("Cannot read {}", path))
+        }
+        None => Ok(day.input().await?),
+    }
+}
+
+// The days which run generation by generation, set up to be stepped through one at a time
+pub fn simulation(challenge: &Challenge, input: &str) -> anyhow::Result<Box<dyn Simulation>> {
+    let lines = input.lines().collect::<Vec<_>>();
+    let part_b = challenge.1 == Part::PartB;
+
+    match challenge.0 {
+        Day::Day11 => day_11::simulation(&lines, part_b),
+        Day::Day17 => day_17::simulation(&lines, part_b),
+        Day::Day23 => day_23::simulation(&lines, part_b),
+        Day::Day24 => day_24::simulation(&lines),
+        day => Err(anyhow!("{} has no simulation to visualize", day)),
+    }
+}
+
+// Still image of a day's result, as opposed to the generations of a simulation
+pub fn picture(day: Day, input: &str) -> anyhow::Result<Grid<Cell>> {
+    let input = input.as_bytes();
+
+    match day {
+        Day::Day20 => day_20::picture(input, &LineIndex::new(input)),
+        day => Err(anyhow!("{} has no picture", day)),
+    }
+}
+
+// Checks the passwords of day 2 against a combination of named policies, line by line
+pub fn password_report(input: &str, policy: &str, parsing: Parsing) -> anyhow::Result<String> {
+    let lines = input.lines().collect::<Vec<_>>();
+    day_02::report(&lines, policy, parsing)
+}
+
+// Intermediate results behind the answer of a challenge, if its day explains them
+pub fn explain(challenge: &Challenge, input: &str) -> anyhow::Result<Option<String>> {
+    EXPLANATIONS.explain(challenge, input)
+}
+
+pub struct Answer {
+    pub value: String,
+    pub duration: Duration,
 }
 
 trait Solution {
-    fn run(&self, challenge: &Challenge, input: &[&str]) -> anyhow::Result<()>;
+    fn run(&self, input: &str) -> anyhow::Result<Answer>;
 }
 
-impl<R: std::fmt::Display> Solution for fn(&[&str]) -> anyhow::Result<R> {
-    fn run(&self, challenge: &Challenge, input: &[&str]) -> anyhow::Result<()> {
-        let start = Instant::now();
-        let result = self(input)?;
-        let duration = start.elapsed();
-        println!("{}: {} (duration = {:?})", challenge, result, duration);
-        Ok(())
+// Solutions either take the input split into lines, or the raw input along with a line index
+trait IntoSolution<Signature> {
+    fn into_solution(self) -> Box<dyn Solution + Sync + 'static>;
+}
+
+struct LineSolution<F>(F);
+struct ByteSolution<F>(F);
+
+impl<F, R> IntoSolution<fn(&[&str]) -> R> for F
+where
+    F: Fn(&[&str]) -> anyhow::Result<R> + Sync + 'static,
+    R: std::fmt::Display,
+{
+    fn into_solution(self) -> Box<dyn Solution + Sync + 'static> {
+        Box::new(LineSolution(self))
+    }
+}
+
+impl<F, R> IntoSolution<fn(&[u8], &LineIndex) -> R> for F
+where
+    F: Fn(&[u8], &LineIndex) -> anyhow::Result<R> + Sync + 'static,
+    R: std::fmt::Display,
+{
+    fn into_solution(self) -> Box<dyn Solution + Sync + 'static> {
+        Box::new(ByteSolution(self))
     }
 }
 
+impl<F, R> Solution for LineSolution<F>
+where
+    F: Fn(&[&str]) -> anyhow::Result<R>,
+    R: std::fmt::Display,
+{
+    fn run(&self, input: &str) -> anyhow::Result<Answer> {
+        let lines =
+            tracing::info_span!("split_lines").in_scope(|| input.lines().collect::<Vec<_>>());
+        let _span = solver_span::<F>().entered();
+        measure(|| (self.0)(&lines))
+    }
+}
+
+impl<F, R> Solution for ByteSolution<F>
+where
+    F: Fn(&[u8], &LineIndex) -> anyhow::Result<R>,
+    R: std::fmt::Display,
+{
+    fn run(&self, input: &str) -> anyhow::Result<Answer> {
+        let input = input.as_bytes();
+        let lines = tracing::info_span!("index_lines").in_scope(|| LineIndex::new(input));
+        let _span = solver_span::<F>().entered();
+        measure(|| (self.0)(input, &lines))
+    }
+}
+
+// Named after the function behind the solution, e.g. advent_of_code_2020::challenge::day_01::part_a
+fn solver_span<F>() -> tracing::Span {
+    tracing::info_span!("solver", function = %std::any::type_name::<F>())
+}
+
+fn measure<R: std::fmt::Display>(
+    func: impl FnOnce() -> anyhow::Result<R>,
+) -> anyhow::Result<Answer> {
+    let start = Instant::now();
+    let result = func()?;
+    let duration = start.elapsed();
+
+    Ok(Answer {
+        value: result.to_string(),
+        duration,
+    })
+}
+
 struct Solutions(HashMap<Challenge, Box<dyn Solution + Sync + 'static>>);
 
 impl Solutions {
@@ -275,22 +450,89 @@ impl Solutions {
         Solutions(HashMap::new())
     }
 
-    fn add<R: std::fmt::Display + 'static>(
-        &mut self,
-        day: Day,
-        part: Part,
-        func: fn(&[&str]) -> anyhow::Result<R>,
-    ) {
-        self.0.insert(Challenge::new(day, part), Box::new(func));
+    fn add<Signature>(&mut self, day: Day, part: Part, func: impl IntoSolution<Signature>) {
+        self.0
+            .insert(Challenge::new(day, part), func.into_solution());
     }
 
-    async fn solve(&self, challenge: &Challenge) -> anyhow::Result<()> {
+    fn get(&self, challenge: &Challenge) -> Result<&(dyn Solution + Sync), Error> {
         let solution = self
             .0
             .get(challenge)
-            .with_context(|| format!("Cannot find solution for {}", challenge))?;
+            .ok_or(Error::UnknownChallenge(*challenge))?;
 
+        Ok(solution.as_ref())
+    }
+
+    #[tracing::instrument(skip_all, fields(challenge = %challenge))]
+    async fn solve(&self, challenge: &Challenge, explain: bool) -> anyhow::Result<()> {
+        let solution = self.get(challenge)?;
         let input = challenge.0.input().await?;
-        solution.run(challenge, &input.lines().collect::<Vec<_>>())
+        let answer = solution.run(&input).map_err(|source| Error::Solver {
+            challenge: *challenge,
+            source,
+        });
+
+        if let Ok(answer) = &answer {
+            println!(
+                "{}: {} (duration = {:?})",
+                challenge, answer.value, answer.duration
+            );
+
+            // The answer is out already, a history which cannot be written should not turn it
+            // into a failure
+            if let Err(error) = crate::history::record(challenge, &input, answer) {
+                eprintln!("Warning: {:#}", error);
+            }
+        }
+
+        // Also when the solution failed, as the explanation may show why, e.g. that no pair adds
+        // up to the target. The solution's error still decides the outcome.
+        if explain {
+            match EXPLANATIONS.explain(challenge, &input) {
+                Ok(Some(explanation)) => {
+                    explanation.lines().for_each(|line| println!("  {}", line))
+                }
+                Ok(None) => println!("  {} has no explanation", challenge),
+                Err(error) if answer.is_ok() => return Err(error),
+                Err(error) => println!("  Cannot explain, {:#}", error),
+            }
+        }
+
+        answer?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(challenge = %challenge))]
+    fn run(&self, challenge: &Challenge, input: &str) -> Result<Answer, Error> {
+        self.get(challenge)?
+            .run(input)
+            .map_err(|source| Error::Solver {
+                challenge: *challenge,
+                source,
+            })
+    }
+}
+
+// Intermediate results of a challenge, which are only worked out on request and after the solution
+// has been timed. They take the same signatures as the solutions, and days whose explanation covers
+// both parts register it for each.
+struct Explanations(HashMap<Challenge, Box<dyn Solution + Sync + 'static>>);
+
+impl Explanations {
+    fn new() -> Self {
+        Explanations(HashMap::new())
+    }
+
+    fn add<Signature>(&mut self, day: Day, part: Part, func: impl IntoSolution<Signature>) {
+        self.0
+            .insert(Challenge::new(day, part), func.into_solution());
+    }
+
+    fn explain(&self, challenge: &Challenge, input: &str) -> anyhow::Result<Option<String>> {
+        self.0
+            .get(challenge)
+            .map(|explanation| Ok(explanation.run(input)?.value))
+            .transpose()
     }
 }
@@ -0,0 +1,181 @@
+use anyhow::{anyhow, Context};
+use std::ops::Range;
+use std::str::FromStr;
+
+pub trait Line {
+    fn is_blank(&self) -> bool;
+}
+
+impl Line for &str {
+    fn is_blank(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl Line for Range<usize> {
+    fn is_blank(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+// Consecutive lines of the input, which remember where they started so errors can point at the
+// line number in the original input
+#[derive(Copy, Clone)]
+pub struct Section<'a, L> {
+    pub start: usize,
+    pub lines: &'a [L],
+}
+
+impl<'a, L> Section<'a, L> {
+    pub fn new(lines: &'a [L]) -> Self {
+        Section { start: 0, lines }
+    }
+
+    pub fn skip(&self, count: usize) -> Self {
+        let count = count.min(self.lines.len());
+
+        Section {
+            start: self.start + count,
+            lines: &self.lines[count..],
+        }
+    }
+
+    pub fn line_number(&self, index: usize) -> usize {
+        self.start + index + 1
+    }
+}
+
+impl<'a, 'b> Section<'a, &'b str> {
+    pub fn line_with<T>(
+        &self,
+        index: usize,
+        parse: impl FnOnce(&'b str) -> anyhow::Result<T>,
+    ) -> anyhow::Result<T> {
+        let number = self.line_number(index);
+        let line = self
+            .lines
+            .get(index)
+            .with_context(|| format!("Missing line {}", number))?;
+
+        parse(line).with_context(|| format!("Invalid input on line {}", number))
+    }
+
+    pub fn line<T>(&self, index: usize) -> anyhow::Result<T>
+    where
+        T: FromStr,
+        T::Err: Into<anyhow::Error>,
+    {
+        self.line_with(index, value)
+    }
+
+    pub fn lines_with<T>(
+        &self,
+        mut parse: impl FnMut(&'b str) -> anyhow::Result<T>,
+    ) -> anyhow::Result<Vec<T>> {
+        (0..self.lines.len())
+            .map(|index| self.line_with(index, &mut parse))
+            .collect()
+    }
+
+    pub fn lines<T>(&self) -> anyhow::Result<Vec<T>>
+    where
+        T: FromStr,
+        T::Err: Into<anyhow::Error>,
+    {
+        self.lines_with(value)
+    }
+}
+
+// Splits the input on blank lines, e.g. into passports or player decks, skipping any extra blank
+// lines such as the one at the end of the input
+pub fn sections<L: Line>(lines: &[L]) -> impl Iterator<Item = Section<'_, L>> {
+    let mut start = 0;
+
+    lines
+        .split(|line| line.is_blank())
+        .map(move |lines| {
+            let section = Section { start, lines };
+            start += lines.len() + 1;
+            section
+        })
+        .filter(|section| !section.lines.is_empty())
+}
+
+pub fn split_sections<L: Line, const N: usize>(lines: &[L]) -> anyhow::Result<[Section<'_, L>; N]> {
+    let sections = sections(lines).collect::<Vec<_>>();
+    let count = sections.len();
+
+    sections.try_into().map_err(|_| {
+        anyhow!(
+            "Expected {} sections separated by blank lines, found {}",
+            N,
+            count
+        )
+    })
+}
+
+pub fn line_with<'a, T>(
+    input: &[&'a str],
+    index: usize,
+    parse: impl FnOnce(&'a str) -> anyhow::Result<T>,
+) -> anyhow::Result<T> {
+    Section::new(input).line_with(index, parse)
+}
+
+pub fn line<T>(input: &[&str], index: usize) -> anyhow::Result<T>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    Section::new(input).line(index)
+}
+
+pub fn lines_with<'a, T>(
+    input: &[&'a str],
+    parse: impl FnMut(&'a str) -> anyhow::Result<T>,
+) -> anyhow::Result<Vec<T>> {
+    Section::new(input).lines_with(parse)
+}
+
+// One value per line, e.g. the expense report of day 1
+pub fn lines<T>(input: &[&str]) -> anyhow::Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    Section::new(input).lines()
+}
+
+pub fn value<T>(string: &str) -> anyhow::Result<T>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    string
+        .parse()
+        .map_err(Into::into)
+        .with_context(|| format!("Cannot parse {:?}", string))
+}
+
+pub fn list_with<'a, T>(
+    string: &'a str,
+    separator: char,
+    parse: impl FnMut(&'a str) -> anyhow::Result<T>,
+) -> anyhow::Result<Vec<T>> {
+    string.split(separator).map(parse).collect()
+}
+
+// Separated values on a single line, e.g. `7,13,x,x,59`
+pub fn list<T>(string: &str, separator: char) -> anyhow::Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    list_with(string, separator, value)
+}
+
+pub fn split_once<'a>(string: &'a str, separator: &str) -> anyhow::Result<(&'a str, &'a str)> {
+    string
+        .split_once(separator)
+        .with_context(|| format!("Expected {:?} in {:?}", separator, string))
+}
@@ -0,0 +1,70 @@
+use super::grid::Grid;
+use std::ops::RangeInclusive;
+
+// What a cell stands for, the renderer decides how that looks
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Shade {
+    Background,
+    Inactive,
+    Active,
+    // Changed state during the last generation
+    Activated,
+    Deactivated,
+    Highlight,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Cell {
+    pub symbol: char,
+    pub shade: Shade,
+}
+
+impl Cell {
+    pub const BLANK: Cell = Cell::new(' ', Shade::Background);
+
+    pub const fn new(symbol: char, shade: Shade) -> Self {
+        Cell { symbol, shade }
+    }
+}
+
+// A day which runs generation by generation, so that it can be watched while it runs
+pub trait Simulation {
+    // Advances by one generation, returns false once the simulation is over
+    fn step(&mut self) -> bool;
+
+    // Coordinates of every dimension beyond the first two, the rendered plane is one slice of them
+    fn slices(&self) -> Vec<RangeInclusive<isize>> {
+        Vec::new()
+    }
+
+    fn render(&self, slice: &[isize]) -> Grid<Cell>;
+
+    // Summary of the current generation, e.g. the number of occupied seats
+    fn status(&self) -> String;
+}
+
+// Shade of a cell which is either active or not, given its state before the last generation
+pub fn shade(active: bool, previous: bool) -> Shade {
+    match (active, previous) {
+        (true, true) => Shade::Active,
+        (true, false) => Shade::Activated,
+        (false, true) => Shade::Deactivated,
+        (false, false) => Shade::Inactive,
+    }
+}
+
+// Every combination of coordinates in the dimensions beyond the plane, a plane has a single slice
+pub fn every_slice(ranges: &[RangeInclusive<isize>]) -> Vec<Vec<isize>> {
+    ranges.iter().fold(vec![Vec::new()], |slices, range| {
+        slices
+            .iter()
+            .flat_map(|slice| {
+                range.clone().map(move |value| {
+                    let mut slice = slice.clone();
+                    slice.push(value);
+                    slice
+                })
+            })
+            .collect()
+    })
+}
@@ -0,0 +1,224 @@
+use crate::challenge::{self, Cell, Challenge, Day, Grid, Shade, Simulation};
+use anyhow::{anyhow, Context};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+const DEFAULT_SCALE: usize = 4;
+const DEFAULT_MAX_FRAMES: usize = 1000;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Format {
+    Ppm,
+    Png,
+}
+
+impl Format {
+    fn extension(&self) -> &'static str {
+        match self {
+            Format::Ppm => "ppm",
+            Format::Png => "png",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "ppm" => Ok(Format::Ppm),
+            "png" => Ok(Format::Png),
+            _ => Err(anyhow!(
+                "{} is not a valid format, expecting ppm or png",
+                string
+            )),
+        }
+    }
+}
+
+// Writes the picture of day 20, or every generation of a grid simulation as numbered frames
+pub async fn run(mut args: impl Iterator<Item = String>) -> anyhow::Result<()> {
+    const USAGE: &str =
+        "expecting export <challenge> <directory> [--format ppm|png] [--scale <n>] [--max-frames <n>] \
+         [--input <file>]";
+
+    let challenge: Challenge = args
+        .next()
+        .with_context(|| format!("Missing challenge, {}", USAGE))?
+        .parse()?;
+
+    let directory = args
+        .next()
+        .with_context(|| format!("Missing directory, {}", USAGE))?;
+
+    let mut format = Format::Png;
+    let mut scale = DEFAULT_SCALE;
+    let mut max_frames = DEFAULT_MAX_FRAMES;
+    let mut path = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .with_context(|| format!("Missing value for {}", arg))
+        };
+
+        match arg.as_str() {
+            "--format" => format = value()?.parse()?,
+            "--scale" => scale = value()?.parse().context("Invalid scale")?,
+            "--max-frames" => max_frames = value()?.parse().context("Invalid frame count")?,
+            "--input" => path = Some(value()?),
+            _ => return Err(anyhow!("Unknown option {}, {}", arg, USAGE)),
+        }
+    }
+
+    let input = challenge::read_input(challenge.day(), path.as_deref()).await?;
+    let directory = Path::new(&directory);
+
+    std::fs::create_dir_all(directory)
+        .with_context(|| format!("Cannot create {}", directory.display()))?;
+
+    let name = challenge.code();
+
+    match challenge.day() {
+        Day::Day20 => {
+            let path = directory.join(name).with_extension(format.extension());
+            write_image(
+                &challenge::picture(Day::Day20, &input)?,
+                scale,
+                format,
+                &path,
+            )?;
+            println!("{}: wrote {}", challenge, path.display());
+        }
+        Day::Day11 | Day::Day17 | Day::Day24 => {
+            let mut simulation = challenge::simulation(&challenge, &input)?;
+            let count = write_frames(
+                simulation.as_mut(),
+                directory,
+                &name,
+                scale,
+                format,
+                max_frames,
+            )?;
+            println!(
+                "{}: wrote {} images to {}",
+                challenge,
+                count,
+                directory.display()
+            );
+        }
+        day => return Err(anyhow!("{} has no grid to export", day)),
+    }
+
+    Ok(())
+}
+
+// Writes every generation as `<name>_<generation>.<extension>`, with one image per slice for the
+// dimensions beyond the plane, e.g. `17b_0003_z-1_w2.png`. Simulations which never settle stop
+// after `max_frames` generations. Returns the number of images written.
+pub fn write_frames(
+    simulation: &mut dyn Simulation,
+    directory: &Path,
+    name: &str,
+    scale: usize,
+    format: Format,
+    max_frames: usize,
+) -> anyhow::Result<usize> {
+    let slices = challenge::every_slice(&simulation.slices());
+
+    let mut count = 0;
+
+    for generation in 0..max_frames {
+        for slice in &slices {
+            let suffix = ["z", "w"]
+                .iter()
+                .zip(slice)
+                .map(|(axis, value)| format!("_{}{}", axis, value))
+                .collect::<String>();
+
+            let file = format!(
+                "{}_{:04}{}.{}",
+                name,
+                generation,
+                suffix,
+                format.extension()
+            );
+            write_image(
+                &simulation.render(slice),
+                scale,
+                format,
+                &directory.join(file),
+            )?;
+            count += 1;
+        }
+
+        if !simulation.step() {
+            break;
+        }
+    }
+
+    Ok(count)
+}
+
+// Every cell becomes a square of scale by scale pixels
+pub fn write_image(
+    grid: &Grid<Cell>,
+    scale: usize,
+    format: Format,
+    path: &Path,
+) -> anyhow::Result<()> {
+    if scale == 0 {
+        return Err(anyhow!("The scale has to be at least 1"));
+    }
+
+    let (width, height) = (grid.width() * scale, grid.height() * scale);
+    let mut pixels = Vec::with_capacity(width * height * 3);
+
+    for row in grid.rows() {
+        let start = pixels.len();
+
+        for cell in row {
+            let color = color(cell.shade);
+
+            for _ in 0..scale {
+                pixels.extend_from_slice(&color);
+            }
+        }
+
+        for _ in 1..scale {
+            pixels.extend_from_within(start..start + width * 3);
+        }
+    }
+
+    let file =
+        std::fs::File::create(path).with_context(|| format!("Cannot create {}", path.display()))?;
+    let mut writer = BufWriter::new(file);
+
+    match format {
+        Format::Ppm => {
+            write!(writer, "P6\n{} {}\n255\n", width, height)?;
+            writer.write_all(&pixels)?;
+        }
+        Format::Png => {
+            let mut encoder = png::Encoder::new(&mut writer, width as u32, height as u32);
+            encoder.set_color(png::ColorType::Rgb);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder.write_header()?.write_image_data(&pixels)?;
+        }
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+fn color(shade: Shade) -> [u8; 3] {
+    match shade {
+        Shade::Background => [16, 24, 48],
+        Shade::Inactive => [96, 104, 120],
+        Shade::Active => [40, 160, 72],
+        Shade::Activated => [140, 240, 120],
+        Shade::Deactivated => [200, 60, 60],
+        Shade::Highlight => [250, 210, 40],
+    }
+}
@@ -0,0 +1,52 @@
+use super::Rng;
+use std::fmt::Write;
+
+const TARGET: u64 = 2020;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(5);
+
+    // One pair and one triple reach the target, the filler values are too large to be part of any
+    let specials = loop {
+        let x = rng.range(100, 600);
+        let y = rng.range(x + 1, (TARGET - x) / 2 - 1);
+        let a = rng.range(700, 1000);
+        let values = [x, y, TARGET - x - y, a, TARGET - a];
+
+        if count_sums(&values, 2) == 1 && count_sums(&values, 3) == 1 {
+            break values;
+        }
+    };
+
+    let min = *specials.iter().min().unwrap();
+    let mut values = specials.to_vec();
+
+    while values.len() < size {
+        values.push(rng.range(TARGET + 1 - min, TARGET - 1));
+    }
+
+    rng.shuffle(&mut values);
+
+    values.iter().fold(String::new(), |mut output, value| {
+        writeln!(output, "{}", value).unwrap();
+        output
+    })
+}
+
+fn count_sums(values: &[u64], k: usize) -> usize {
+    let mut count = 0;
+
+    for i in 0..values.len() {
+        for j in i + 1..values.len() {
+            if k == 2 {
+                count += (values[i] + values[j] == TARGET) as usize;
+            } else {
+                for l in j + 1..values.len() {
+                    count += (values[i] + values[j] + values[l] == TARGET) as usize;
+                }
+            }
+        }
+    }
+
+    count
+}
@@ -0,0 +1,32 @@
+use super::Rng;
+use std::fmt::Write;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+
+    for _ in 0..size {
+        let letter = rng.range(b'a' as u64, b'z' as u64) as u8;
+        let length = rng.range(2, 20) as usize;
+        let first = rng.range(1, length as u64 - 1);
+        let second = rng.range(first + 1, length as u64);
+
+        let password = (0..length)
+            .map(|_| {
+                if rng.chance(0.3) {
+                    letter as char
+                } else {
+                    rng.range(b'a' as u64, b'z' as u64) as u8 as char
+                }
+            })
+            .collect::<String>();
+
+        writeln!(
+            output,
+            "{}-{} {}: {}",
+            first, second, letter as char, password
+        )
+        .unwrap();
+    }
+
+    output
+}
@@ -0,0 +1,18 @@
+use super::Rng;
+
+const WIDTH: usize = 31;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::with_capacity((WIDTH + 1) * size);
+
+    for y in 0..size {
+        for x in 0..WIDTH {
+            let tree = (x, y) != (0, 0) && rng.chance(0.25);
+            output.push(if tree { '#' } else { '.' });
+        }
+
+        output.push('\n');
+    }
+
+    output
+}
@@ -0,0 +1,76 @@
+use super::Rng;
+
+const EYE_COLORS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+
+    for i in 0..size {
+        if i > 0 {
+            output.push('\n');
+        }
+
+        let mut fields = Vec::with_capacity(8);
+        let valid = rng.chance(0.6);
+        let invalid = if valid { usize::MAX } else { rng.index(9) };
+
+        for (index, name) in ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"]
+            .iter()
+            .enumerate()
+        {
+            if index == invalid && rng.chance(0.5) || name == &"cid" && rng.chance(0.5) {
+                continue;
+            }
+
+            let value = if index == invalid {
+                invalid_value(rng, index)
+            } else {
+                valid_value(rng, index)
+            };
+
+            fields.push(format!("{}:{}", name, value));
+        }
+
+        rng.shuffle(&mut fields);
+
+        for (i, field) in fields.iter().enumerate() {
+            if i > 0 {
+                output.push(if rng.chance(0.3) { '\n' } else { ' ' });
+            }
+
+            output.push_str(field);
+        }
+
+        output.push('\n');
+    }
+
+    output
+}
+
+fn valid_value(rng: &mut Rng, index: usize) -> String {
+    match index {
+        0 => rng.range(1920, 2002).to_string(),
+        1 => rng.range(2010, 2020).to_string(),
+        2 => rng.range(2020, 2030).to_string(),
+        3 if rng.chance(0.5) => format!("{}cm", rng.range(150, 193)),
+        3 => format!("{}in", rng.range(59, 76)),
+        4 => format!("#{:06x}", rng.range(0, 0xffffff)),
+        5 => rng.choose(&EYE_COLORS).to_string(),
+        6 => format!("{:09}", rng.range(0, 999999999)),
+        _ => rng.range(50, 350).to_string(),
+    }
+}
+
+fn invalid_value(rng: &mut Rng, index: usize) -> String {
+    match index {
+        0 => rng.range(1900, 1919).to_string(),
+        1 => rng.range(2021, 2030).to_string(),
+        2 => rng.range(1990, 2019).to_string(),
+        3 if rng.chance(0.5) => format!("{}cm", rng.range(100, 149)),
+        3 => rng.range(50, 200).to_string(),
+        4 => format!("{:06x}", rng.range(0, 0xffffff)),
+        5 => "xry".to_string(),
+        6 => format!("{:010}", rng.range(0, 9999999999)),
+        _ => rng.range(50, 350).to_string(),
+    }
+}
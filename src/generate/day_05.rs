@@ -0,0 +1,34 @@
+use super::Rng;
+
+const SEAT_COUNT: u64 = 1024;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = (size as u64).clamp(3, SEAT_COUNT - 16);
+    let first = rng.range(8, SEAT_COUNT - 8 - size);
+    let missing = rng.range(first + 1, first + size - 2);
+
+    let mut seats = (first..first + size)
+        .filter(|&seat| seat != missing)
+        .collect::<Vec<_>>();
+
+    rng.shuffle(&mut seats);
+
+    let mut output = String::with_capacity(seats.len() * 11);
+
+    for seat in seats {
+        for bit in (0..10).rev() {
+            let set = (seat >> bit) & 1 == 1;
+
+            output.push(match (bit >= 3, set) {
+                (true, false) => 'F',
+                (true, true) => 'B',
+                (false, false) => 'L',
+                (false, true) => 'R',
+            });
+        }
+
+        output.push('\n');
+    }
+
+    output
+}
@@ -0,0 +1,31 @@
+use super::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+
+    for i in 0..size {
+        if i > 0 {
+            output.push('\n');
+        }
+
+        let common = rng.range(0, (1 << 26) - 1) & rng.range(0, (1 << 26) - 1);
+
+        for _ in 0..rng.range(1, 5) {
+            let mut answers = common | rng.range(0, (1 << 26) - 1) & rng.range(0, (1 << 26) - 1);
+
+            if answers == 0 {
+                answers = 1 << rng.range(0, 25);
+            }
+
+            for bit in 0..26 {
+                if (answers >> bit) & 1 == 1 {
+                    output.push((b'a' + bit as u8) as char);
+                }
+            }
+
+            output.push('\n');
+        }
+    }
+
+    output
+}
@@ -0,0 +1,69 @@
+use super::Rng;
+use std::collections::HashSet;
+
+const TARGET: &str = "shiny gold";
+const MAX_TOTAL: u64 = 1_000_000_000_000;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(2);
+    let mut names = HashSet::new();
+    names.insert(TARGET.to_string());
+
+    while names.len() < size {
+        names.insert(format!("{} {}", rng.word(2), rng.word(2)));
+    }
+
+    names.remove(TARGET);
+    let mut names = names.into_iter().collect::<Vec<_>>();
+    names.sort();
+    rng.shuffle(&mut names);
+
+    // Bags can only contain bags which come later, which keeps the graph acyclic
+    let target = rng.range(size as u64 / 2, size as u64 * 3 / 4) as usize;
+    names.insert(target.min(names.len()), TARGET.to_string());
+
+    let mut totals = vec![0u64; size];
+    let mut rules = vec![String::new(); size];
+
+    for i in (0..size).rev() {
+        let mut children = Vec::new();
+        let mut total = 0u64;
+
+        if i + 1 < size {
+            for _ in 0..rng.range(0, 4) {
+                let j = rng.range(i as u64 + 1, (i + 8).min(size - 1) as u64) as usize;
+                let count = rng.range(1, 5);
+                let added = count * (1 + totals[j]);
+
+                if children.iter().any(|(child, _)| *child == j) || total + added > MAX_TOTAL {
+                    continue;
+                }
+
+                total += added;
+                children.push((j, count));
+            }
+        }
+
+        totals[i] = total;
+
+        rules[i] = if children.is_empty() {
+            format!("{} bags contain no other bags.", names[i])
+        } else {
+            let children = children
+                .iter()
+                .map(|&(j, count)| {
+                    let suffix = if count == 1 { "" } else { "s" };
+                    format!("{} {} bag{}", count, names[j], suffix)
+                })
+                .collect::<Vec<_>>();
+
+            format!("{} bags contain {}.", names[i], children.join(", "))
+        };
+    }
+
+    rng.shuffle(&mut rules);
+
+    rules
+        .iter()
+        .fold(String::new(), |output, rule| output + rule + "\n")
+}
@@ -0,0 +1,207 @@
+use super::Rng;
+
+// The fix may have to jump right past the end of the program, and the puzzle's values are i16
+const MAX_SIZE: usize = i16::MAX as usize;
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Operation {
+    Acc,
+    Jmp,
+    Nop,
+}
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(4, MAX_SIZE);
+
+    let program = loop {
+        if let Some(program) = try_generate(rng, size) {
+            break program;
+        }
+    };
+
+    program
+        .iter()
+        .fold(String::new(), |output, &(operation, value)| {
+            let name = match operation {
+                Operation::Acc => "acc",
+                Operation::Jmp => "jmp",
+                Operation::Nop => "nop",
+            };
+
+            output + &format!("{} {:+}\n", name, value)
+        })
+}
+
+// Starts from a random looping program and rewrites it until exactly one swapped instruction lets
+// it terminate. Random programs almost never have a single fix once they grow past a few hundred
+// instructions, so retrying alone does not scale.
+fn try_generate(rng: &mut Rng, size: usize) -> Option<Vec<(Operation, i64)>> {
+    let mut program = (0..size)
+        .map(|i| random_instruction(rng, i, size))
+        .collect::<Vec<_>>();
+
+    let terminates = find_terminating(&program);
+
+    if terminates[0] {
+        return None;
+    }
+
+    let executed = find_executed(&program);
+    let nops = executed
+        .iter()
+        .copied()
+        .filter(|&position| program[position].0 == Operation::Nop)
+        .collect::<Vec<_>>();
+
+    if nops.is_empty() {
+        return None;
+    }
+
+    let fix = *rng.choose(&nops);
+
+    // An acc keeps the control flow of a nop, but cannot be swapped
+    for &position in &nops {
+        if position != fix {
+            program[position] = (Operation::Acc, rng.range(0, 100) as i64 - 50);
+        }
+    }
+
+    // Swapping a jmp runs the next instruction instead, which is made to jump back into the loop.
+    // The next instruction is never executed itself, as it would otherwise be part of the loop.
+    for &position in &executed {
+        if program[position].0 == Operation::Jmp && terminates[position + 1] {
+            if position + 1 == size {
+                return None;
+            }
+
+            program[position + 1] = (Operation::Jmp, -1);
+        }
+    }
+
+    let terminates = find_terminating(&program);
+    let targets = (fix.saturating_sub(20)..=(fix + 20).min(size))
+        .filter(|&target| terminates[target])
+        .collect::<Vec<_>>();
+
+    let target = if targets.is_empty() {
+        size
+    } else {
+        *rng.choose(&targets)
+    };
+
+    program[fix].1 = target as i64 - fix as i64;
+
+    (count_fixes(&program) == 1).then_some(program)
+}
+
+fn random_instruction(rng: &mut Rng, position: usize, size: usize) -> (Operation, i64) {
+    let operation = match rng.range(0, 9) {
+        0..=4 => Operation::Acc,
+        5..=7 => Operation::Jmp,
+        _ => Operation::Nop,
+    };
+
+    let value = if operation == Operation::Acc {
+        rng.range(0, 100) as i64 - 50
+    } else {
+        let min = -(position.min(20) as i64);
+        let max = (size - position).min(20) as i64;
+        let value = rng.range(0, (max - min) as u64) as i64 + min;
+        if value == 0 {
+            1
+        } else {
+            value
+        }
+    };
+
+    (operation, value)
+}
+
+// The position after an instruction, if it stays within the program or lands right after its end
+fn next(program: &[(Operation, i64)], position: usize, operation: Operation) -> Option<usize> {
+    let offset = match operation {
+        Operation::Jmp => program[position].1,
+        _ => 1,
+    };
+
+    position
+        .checked_add_signed(offset as isize)
+        .filter(|&position| position <= program.len())
+}
+
+// Whether the program terminates when started from each position, the end included
+fn find_terminating(program: &[(Operation, i64)]) -> Vec<bool> {
+    let mut terminates = vec![None; program.len() + 1];
+    terminates[program.len()] = Some(true);
+
+    for start in 0..program.len() {
+        let mut path = Vec::new();
+        let mut position = Some(start);
+
+        // Positions on the current path count as looping until the walk is resolved
+        let result = loop {
+            match position {
+                Some(current) => match terminates[current] {
+                    Some(result) => break result,
+                    None => {
+                        terminates[current] = Some(false);
+                        path.push(current);
+                        position = next(program, current, program[current].0);
+                    }
+                },
+                None => break false,
+            }
+        };
+
+        for current in path {
+            terminates[current] = Some(result);
+        }
+    }
+
+    terminates
+        .into_iter()
+        .map(|result| result.unwrap())
+        .collect()
+}
+
+// The positions executed by the program until it repeats an instruction or leaves the program
+fn find_executed(program: &[(Operation, i64)]) -> Vec<usize> {
+    let mut visited = vec![false; program.len()];
+    let mut executed = Vec::new();
+    let mut position = 0;
+
+    while position < program.len() && !visited[position] {
+        visited[position] = true;
+        executed.push(position);
+
+        match next(program, position, program[position].0) {
+            Some(next) => position = next,
+            None => break,
+        }
+    }
+
+    executed
+}
+
+// Only the executed instructions matter, and swapping one of them fixes the program exactly when
+// the swapped instruction leads to a position which terminates
+fn count_fixes(program: &[(Operation, i64)]) -> usize {
+    let terminates = find_terminating(program);
+
+    if terminates[0] {
+        return 0;
+    }
+
+    find_executed(program)
+        .into_iter()
+        .filter(|&position| {
+            let swapped = match program[position].0 {
+                Operation::Acc => return false,
+                Operation::Jmp => Operation::Nop,
+                Operation::Nop => Operation::Jmp,
+            };
+
+            next(program, position, swapped).is_some_and(|next| terminates[next])
+        })
+        .count()
+}
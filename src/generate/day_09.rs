@@ -0,0 +1,69 @@
+use super::Rng;
+
+const PREAMBLE_SIZE: usize = 25;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(PREAMBLE_SIZE + 5);
+
+    let numbers = loop {
+        if let Some(numbers) = try_generate(rng, size) {
+            break numbers;
+        }
+    };
+
+    numbers.iter().fold(String::new(), |output, number| {
+        output + &format!("{}\n", number)
+    })
+}
+
+fn try_generate(rng: &mut Rng, size: usize) -> Option<Vec<u64>> {
+    let invalid_at = rng.range((PREAMBLE_SIZE + 2) as u64, size as u64 - 1) as usize;
+    let mut numbers = Vec::with_capacity(size);
+
+    while numbers.len() < PREAMBLE_SIZE {
+        let value = rng.range(1, 50);
+
+        if !numbers.contains(&value) {
+            numbers.push(value);
+        }
+    }
+
+    while numbers.len() < size {
+        let window = &numbers[numbers.len() - PREAMBLE_SIZE..];
+
+        let value = if numbers.len() == invalid_at {
+            let length = rng.range(2, 17).min(numbers.len() as u64) as usize;
+            let start = rng.range(0, (numbers.len() - length) as u64) as usize;
+            let value = numbers[start..start + length].iter().sum::<u64>();
+
+            if is_valid(window, value) {
+                return None;
+            }
+
+            value
+        } else {
+            // Prefers the smaller half of the window to keep the numbers from growing too fast
+            let mut sorted = window.to_vec();
+            sorted.sort_unstable();
+            let x = sorted[rng.index(PREAMBLE_SIZE * 2 / 3)];
+            let y = sorted[rng.index(PREAMBLE_SIZE * 2 / 3)];
+
+            if x == y {
+                continue;
+            }
+
+            x.checked_add(y)?
+        };
+
+        numbers.push(value);
+    }
+
+    Some(numbers)
+}
+
+fn is_valid(window: &[u64], value: u64) -> bool {
+    window
+        .iter()
+        .enumerate()
+        .any(|(i, &x)| window[i + 1..].iter().any(|&y| x != y && x + y == value))
+}
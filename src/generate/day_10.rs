@@ -0,0 +1,34 @@
+use super::Rng;
+
+// Number of arrangements contributed by a run of consecutive 1 jolt differences
+const RUN_ARRANGEMENTS: [u64; 5] = [1, 1, 1, 2, 4];
+const MAX_ARRANGEMENTS: f64 = 1e15;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(2);
+    let mut adapters = Vec::with_capacity(size);
+    let mut jolts = 0u64;
+    let mut arrangements = 1f64;
+
+    while adapters.len() < size {
+        let mut run = rng.range(1, 4) as usize;
+        run = run.min(size - adapters.len());
+
+        if arrangements * RUN_ARRANGEMENTS[run] as f64 > MAX_ARRANGEMENTS {
+            run = 1;
+        }
+
+        arrangements *= RUN_ARRANGEMENTS[run] as f64;
+
+        for i in 0..run {
+            jolts += if i == 0 && !adapters.is_empty() { 3 } else { 1 };
+            adapters.push(jolts);
+        }
+    }
+
+    rng.shuffle(&mut adapters);
+
+    adapters.iter().fold(String::new(), |output, adapter| {
+        output + &format!("{}\n", adapter)
+    })
+}
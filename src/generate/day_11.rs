@@ -0,0 +1,109 @@
+use super::Rng;
+
+const DIRECTIONS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+// Some random layouts keep flipping between two states, which puzzle inputs never do, so those are
+// generated again
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let seats = loop {
+        let seats = (0..size)
+            .map(|_| (0..size).map(|_| rng.chance(0.75)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        if settles(&seats, false) && settles(&seats, true) {
+            break seats;
+        }
+    };
+
+    let mut output = String::with_capacity((size + 1) * size);
+
+    for row in seats {
+        output.extend(row.iter().map(|&seat| if seat { 'L' } else { '.' }));
+        output.push('\n');
+    }
+
+    output
+}
+
+// Layouts which do not settle flip between two states, so any state but the one two generations
+// back is progress
+fn settles(seats: &[Vec<bool>], extended: bool) -> bool {
+    let neighbors = find_neighbors(seats, extended);
+    let limit = if extended { 5 } else { 4 };
+
+    let mut before = Vec::new();
+    let mut occupied = vec![false; neighbors.len()];
+
+    loop {
+        let next = neighbors
+            .iter()
+            .enumerate()
+            .map(|(seat, neighbors)| {
+                let count = neighbors.iter().filter(|&&other| occupied[other]).count();
+                count == 0 || occupied[seat] && count < limit
+            })
+            .collect::<Vec<_>>();
+
+        if next == occupied {
+            return true;
+        }
+
+        if next == before {
+            return false;
+        }
+
+        before = std::mem::replace(&mut occupied, next);
+    }
+}
+
+// The seats every seat looks at, by their position in reading order
+fn find_neighbors(seats: &[Vec<bool>], extended: bool) -> Vec<Vec<usize>> {
+    let size = seats.len() as isize;
+    let mut indices = vec![vec![usize::MAX; seats.len()]; seats.len()];
+    let mut count = 0;
+
+    for (y, row) in seats.iter().enumerate() {
+        for (x, _) in row.iter().enumerate().filter(|(_, seat)| **seat) {
+            indices[y][x] = count;
+            count += 1;
+        }
+    }
+
+    let mut neighbors = Vec::with_capacity(count);
+
+    for (y, row) in seats.iter().enumerate() {
+        for (x, _) in row.iter().enumerate().filter(|(_, seat)| **seat) {
+            let visible = DIRECTIONS.iter().filter_map(|(dx, dy)| {
+                let (mut x, mut y) = (x as isize + dx, y as isize + dy);
+
+                while (0..size).contains(&x) && (0..size).contains(&y) {
+                    let index = indices[y as usize][x as usize];
+
+                    if index != usize::MAX {
+                        return Some(index);
+                    } else if !extended {
+                        return None;
+                    }
+
+                    x += dx;
+                    y += dy;
+                }
+
+                None
+            });
+
+            neighbors.push(visible.collect());
+        }
+    }
+
+    neighbors
+}
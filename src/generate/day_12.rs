@@ -0,0 +1,18 @@
+use super::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+
+    for _ in 0..size {
+        let action = *rng.choose(b"NSEWLRFFF") as char;
+
+        let amount = match action {
+            'L' | 'R' => rng.range(1, 3) * 90,
+            _ => rng.range(1, 100),
+        };
+
+        output.push_str(&format!("{}{}\n", action, amount));
+    }
+
+    output
+}
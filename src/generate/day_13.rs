@@ -0,0 +1,36 @@
+use super::Rng;
+
+const PRIMES: [u64; 20] = [
+    7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 419, 457, 523, 601,
+];
+const MAX_PRODUCT: u64 = 1 << 56;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(2);
+    let mut primes = PRIMES.to_vec();
+    rng.shuffle(&mut primes);
+
+    let mut product = 1u64;
+    let mut buses = vec![None; size];
+
+    for (i, prime) in primes.into_iter().enumerate() {
+        if product > MAX_PRODUCT / prime {
+            continue;
+        }
+
+        // The first bus always leaves at the timestamp itself
+        let position = if i == 0 { 0 } else { rng.index(size) };
+
+        if buses[position].is_none() {
+            product *= prime;
+            buses[position] = Some(prime);
+        }
+    }
+
+    let buses = buses
+        .iter()
+        .map(|bus| bus.map_or("x".to_string(), |id| id.to_string()))
+        .collect::<Vec<_>>();
+
+    format!("{}\n{}\n", rng.range(1_000_000, 1_010_000), buses.join(","))
+}
@@ -0,0 +1,32 @@
+use super::Rng;
+
+const BIT_COUNT: usize = 36;
+const MAX_FLOATING: usize = 9;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+    let mut lines = 0;
+
+    while lines < size.max(2) {
+        let floating = rng.range(0, MAX_FLOATING as u64) as usize;
+        let mut mask = (0..BIT_COUNT)
+            .map(|_| if rng.chance(0.5) { '1' } else { '0' })
+            .collect::<Vec<_>>();
+
+        for _ in 0..floating {
+            mask[rng.index(BIT_COUNT)] = 'X';
+        }
+
+        output.push_str(&format!("mask = {}\n", mask.iter().collect::<String>()));
+        lines += 1;
+
+        for _ in 0..rng.range(1, 6) {
+            let address = rng.range(0, 65535);
+            let value = rng.range(0, (1 << 30) - 1);
+            output.push_str(&format!("mem[{}] = {}\n", address, value));
+            lines += 1;
+        }
+    }
+
+    output
+}
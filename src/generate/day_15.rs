@@ -0,0 +1,14 @@
+use super::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(1, 20);
+    let mut numbers = (0..20u64).collect::<Vec<_>>();
+    rng.shuffle(&mut numbers);
+
+    let numbers = numbers[..size]
+        .iter()
+        .map(|number| number.to_string())
+        .collect::<Vec<_>>();
+
+    format!("{}\n", numbers.join(","))
+}
@@ -0,0 +1,116 @@
+use super::Rng;
+
+const FIELDS: [&str; 20] = [
+    "departure location",
+    "departure station",
+    "departure platform",
+    "departure track",
+    "departure date",
+    "departure time",
+    "arrival location",
+    "arrival station",
+    "arrival platform",
+    "arrival track",
+    "class",
+    "duration",
+    "price",
+    "route",
+    "row",
+    "seat",
+    "train",
+    "type",
+    "wagon",
+    "zone",
+];
+
+const MIN_VALUE: u64 = 25;
+const MAX_VALUE: u64 = 974;
+const STEP: u64 = 30;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let count = FIELDS.len();
+
+    // A field with rank r accepts the columns of every field with rank >= r, so resolving the
+    // fields with the fewest candidates first always succeeds
+    let mut ranks = (0..count).collect::<Vec<_>>();
+    let mut columns = (0..count).collect::<Vec<_>>();
+    rng.shuffle(&mut ranks);
+    rng.shuffle(&mut columns);
+
+    let lower = |rank: usize| MIN_VALUE + rank as u64 * STEP;
+    let gaps = (0..count)
+        .map(|rank| rng.range(lower(rank) + 2, MAX_VALUE - 2))
+        .collect::<Vec<_>>();
+
+    let mut output = String::new();
+
+    for (field, name) in FIELDS.iter().enumerate() {
+        let rank = ranks[field];
+        let gap = gaps[rank];
+        let line = format!(
+            "{}: {}-{} or {}-{}\n",
+            name,
+            lower(rank),
+            gap - 1,
+            gap + 1,
+            MAX_VALUE
+        );
+        output.push_str(&line);
+    }
+
+    // A value in a column only has to satisfy the fields which accept that column
+    let random_value = |rng: &mut Rng, rank: usize, narrow: bool| loop {
+        let max = if narrow && rank + 1 < count {
+            lower(rank + 1) - 1
+        } else {
+            MAX_VALUE
+        };
+
+        let value = rng.range(lower(rank), max);
+
+        if !gaps.contains(&value) {
+            break value;
+        }
+    };
+
+    let ticket = |rng: &mut Rng, narrow: bool| {
+        let mut values = vec![0; count];
+
+        for field in 0..count {
+            values[columns[field]] = random_value(rng, ranks[field], narrow);
+        }
+
+        values
+    };
+
+    let format = |values: &[u64]| {
+        let values = values
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>();
+        values.join(",") + "\n"
+    };
+
+    output.push_str("\nyour ticket:\n");
+    output.push_str(&format(&ticket(rng, true)));
+    output.push_str("\nnearby tickets:\n");
+
+    for _ in 0..size {
+        let narrow = rng.chance(0.2);
+        let mut values = ticket(rng, narrow);
+
+        if rng.chance(0.25) {
+            let invalid = if rng.chance(0.5) {
+                rng.range(0, MIN_VALUE - 1)
+            } else {
+                rng.range(MAX_VALUE + 1, 999)
+            };
+
+            values[rng.index(count)] = invalid;
+        }
+
+        output.push_str(&format(&values));
+    }
+
+    output
+}
@@ -0,0 +1,18 @@
+use super::Rng;
+
+const MAX_SIZE: usize = 8;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(1, MAX_SIZE);
+    let mut output = String::with_capacity((size + 1) * size);
+
+    for _ in 0..size {
+        for _ in 0..size {
+            output.push(if rng.chance(0.45) { '#' } else { '.' });
+        }
+
+        output.push('\n');
+    }
+
+    output
+}
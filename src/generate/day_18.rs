@@ -0,0 +1,32 @@
+use super::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+
+    for _ in 0..size {
+        let mut budget = 14;
+        expression(rng, 0, &mut budget, &mut output);
+        output.push('\n');
+    }
+
+    output
+}
+
+fn expression(rng: &mut Rng, depth: usize, budget: &mut usize, output: &mut String) {
+    let operands = rng.range(2, 5);
+
+    for i in 0..operands {
+        if i > 0 {
+            output.push_str(if rng.chance(0.5) { " + " } else { " * " });
+        }
+
+        if depth < 2 && *budget > 4 && rng.chance(0.25) {
+            output.push('(');
+            expression(rng, depth + 1, budget, output);
+            output.push(')');
+        } else {
+            *budget = budget.saturating_sub(1);
+            output.push((b'0' + rng.range(1, 9) as u8) as char);
+        }
+    }
+}
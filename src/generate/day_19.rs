@@ -0,0 +1,133 @@
+use super::Rng;
+
+const LEVELS: usize = 3;
+const MIN_RULES: usize = 43;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    // Rules are built bottom up, every rule on level n matches strings of length 2^n
+    let mut levels: Vec<Vec<Vec<Vec<usize>>>> = vec![Vec::new(); LEVELS + 1];
+    let mut count = 2;
+    levels[0] = vec![Vec::new(), Vec::new()];
+
+    let ids = |levels: &Vec<Vec<Vec<Vec<usize>>>>, level: usize| {
+        let offset = levels[..level]
+            .iter()
+            .map(|rules| rules.len())
+            .sum::<usize>();
+        offset..offset + levels[level].len()
+    };
+
+    for level in 1..=LEVELS {
+        let previous = ids(&levels, level - 1).collect::<Vec<_>>();
+        let rules = if level == LEVELS {
+            2
+        } else {
+            rng.range(8, 14) as usize
+        };
+
+        for _ in 0..rules {
+            let alternatives = rng.range(1, 2);
+            let rule = (0..alternatives)
+                .map(|_| vec![*rng.choose(&previous), *rng.choose(&previous)])
+                .collect();
+            levels[level].push(rule);
+            count += 1;
+        }
+    }
+
+    // Renumbers the rules, keeping 0, 8 and 11 free and using 42 and 31 for the top level
+    let total = count.max(MIN_RULES - 3) + 3;
+    let mut numbers = (0..total)
+        .filter(|id| ![0, 8, 11, 42, 31].contains(id))
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut numbers);
+
+    let top = ids(&levels, LEVELS).collect::<Vec<_>>();
+    let mut mapping = vec![0; count];
+    mapping[top[0]] = 42;
+    mapping[top[1]] = 31;
+
+    for (id, number) in mapping.iter_mut().enumerate() {
+        if !top.contains(&id) {
+            *number = numbers.pop().unwrap();
+        }
+    }
+
+    let mut rules = vec![
+        "0: 8 11".to_string(),
+        "8: 42".to_string(),
+        "11: 42 31".to_string(),
+    ];
+
+    rules.push(format!("{}: \"a\"", mapping[0]));
+    rules.push(format!("{}: \"b\"", mapping[1]));
+
+    for level in 1..=LEVELS {
+        for (id, rule) in ids(&levels, level).zip(&levels[level]) {
+            let alternatives = rule
+                .iter()
+                .map(|pattern| format!("{} {}", mapping[pattern[0]], mapping[pattern[1]]))
+                .collect::<Vec<_>>();
+
+            rules.push(format!("{}: {}", mapping[id], alternatives.join(" | ")));
+        }
+    }
+
+    // Unused ids still need a rule, as the ids have to be dense
+    for id in numbers {
+        rules.push(format!("{}: \"a\"", id));
+    }
+
+    rng.shuffle(&mut rules);
+
+    let mut output = rules.join("\n");
+    output.push_str("\n\n");
+
+    for _ in 0..size {
+        let message = match rng.range(0, 2) {
+            0 => {
+                let repeat = rng.range(1, 3);
+                let mut message = expand_times(rng, &levels, top[0], repeat + 1);
+                message.push_str(&expand_times(rng, &levels, top[1], repeat.min(2)));
+                message
+            }
+            1 => {
+                let mut message = expand_times(rng, &levels, top[0], 2);
+                message.push_str(&expand_times(rng, &levels, top[1], 1));
+                message
+            }
+            _ => (0..rng.range(8, 40))
+                .map(|_| if rng.chance(0.5) { 'a' } else { 'b' })
+                .collect(),
+        };
+
+        output.push_str(&message);
+        output.push('\n');
+    }
+
+    output
+}
+
+fn expand_times(rng: &mut Rng, levels: &[Vec<Vec<Vec<usize>>>], id: usize, times: u64) -> String {
+    (0..times).map(|_| expand(rng, levels, id)).collect()
+}
+
+fn expand(rng: &mut Rng, levels: &[Vec<Vec<Vec<usize>>>], id: usize) -> String {
+    let mut offset = 0;
+
+    for (level, rules) in levels.iter().enumerate() {
+        if id < offset + rules.len() {
+            if level == 0 {
+                return if id == 0 { "a" } else { "b" }.to_string();
+            }
+
+            let rule = &rules[id - offset];
+            let pattern = rng.choose(rule).clone();
+            return pattern.iter().map(|&id| expand(rng, levels, id)).collect();
+        }
+
+        offset += rules.len();
+    }
+
+    unreachable!()
+}
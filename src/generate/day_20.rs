@@ -0,0 +1,240 @@
+use super::Rng;
+use std::collections::HashSet;
+
+const TILE_SIZE: usize = 10;
+const FRAME_SIZE: usize = 8;
+// Edges are unique up to flipping, which leaves room for at most 496 of them, so the puzzle's own
+// 12x12 tiles is as large as the generator reliably goes
+const MAX_SIZE: usize = 12;
+const MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(3, MAX_SIZE);
+
+    let borders = loop {
+        if let Some(borders) = generate_borders(rng, size) {
+            break borders;
+        }
+    };
+
+    let image = generate_image(rng, size * FRAME_SIZE);
+    let mut ids = HashSet::new();
+    let mut tiles = Vec::with_capacity(size * size);
+
+    for y in 0..size {
+        for x in 0..size {
+            let mut tile = vec![vec![false; TILE_SIZE]; TILE_SIZE];
+
+            tile[0].clone_from(&borders.horizontal[y][x]);
+            tile[TILE_SIZE - 1].clone_from(&borders.horizontal[y + 1][x]);
+
+            for (i, row) in tile.iter_mut().enumerate() {
+                row[0] = borders.vertical[y][x][i];
+                row[TILE_SIZE - 1] = borders.vertical[y][x + 1][i];
+            }
+
+            for row in 0..FRAME_SIZE {
+                for column in 0..FRAME_SIZE {
+                    tile[row + 1][column + 1] =
+                        image[y * FRAME_SIZE + row][x * FRAME_SIZE + column];
+                }
+            }
+
+            let rotations = rng.range(0, 3);
+            let flipped = rng.chance(0.5);
+
+            // Puzzle inputs have a corner whose outer edges are already at the top and the left,
+            // which solutions may start from
+            if (x, y) != (0, 0) {
+                for _ in 0..rotations {
+                    tile = rotate(&tile);
+                }
+
+                if flipped {
+                    tile.reverse();
+                }
+            }
+
+            let id = loop {
+                let id = rng.range(1000, 9999);
+
+                if ids.insert(id) {
+                    break id;
+                }
+            };
+
+            tiles.push((id, tile));
+        }
+    }
+
+    rng.shuffle(&mut tiles);
+
+    let mut output = String::new();
+
+    for (id, tile) in tiles {
+        output.push_str(&format!("Tile {}:\n", id));
+
+        for row in tile {
+            output.extend(row.iter().map(|&pixel| if pixel { '#' } else { '.' }));
+            output.push('\n');
+        }
+
+        output.push('\n');
+    }
+
+    output
+}
+
+struct Borders {
+    // horizontal[y][x] is the top edge of tile (x, y), read left to right
+    horizontal: Vec<Vec<Vec<bool>>>,
+    // vertical[y][x] is the left edge of tile (x, y), read top to bottom
+    vertical: Vec<Vec<Vec<bool>>>,
+}
+
+// Every edge has to be unique, even when flipped, so only neighbouring tiles match
+fn generate_borders(rng: &mut Rng, size: usize) -> Option<Borders> {
+    let corners = (0..=size)
+        .map(|_| (0..=size).map(|_| rng.chance(0.5)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let mut used = HashSet::new();
+
+    let mut edge = |rng: &mut Rng, start: bool, end: bool| {
+        for _ in 0..1000 {
+            let mut edge = vec![start];
+            edge.extend((0..TILE_SIZE - 2).map(|_| rng.chance(0.5)));
+            edge.push(end);
+
+            let reversed = edge.iter().rev().copied().collect::<Vec<_>>();
+
+            if edge != reversed && !used.contains(&edge) && !used.contains(&reversed) {
+                used.insert(reversed);
+                used.insert(edge.clone());
+                return Some(edge);
+            }
+        }
+
+        None
+    };
+
+    let mut horizontal = vec![vec![Vec::new(); size]; size + 1];
+    let mut vertical = vec![vec![Vec::new(); size + 1]; size];
+
+    for y in 0..=size {
+        for x in 0..size {
+            horizontal[y][x] = edge(rng, corners[y][x], corners[y][x + 1])?;
+        }
+    }
+
+    for y in 0..size {
+        for x in 0..=size {
+            vertical[y][x] = edge(rng, corners[y][x], corners[y + 1][x])?;
+        }
+    }
+
+    Some(Borders {
+        horizontal,
+        vertical,
+    })
+}
+
+// Monsters only show in one orientation of the image, so noise which happens to form one in
+// another orientation is generated again
+fn generate_image(rng: &mut Rng, size: usize) -> Vec<Vec<bool>> {
+    let mut image = loop {
+        let image = place_monsters(rng, size);
+
+        if count_orientations_with_monsters(&image) == 1 {
+            break image;
+        }
+    };
+
+    for _ in 0..rng.range(0, 3) {
+        image = rotate(&image);
+    }
+
+    if rng.chance(0.5) {
+        image.reverse();
+    }
+
+    image
+}
+
+fn place_monsters(rng: &mut Rng, size: usize) -> Vec<Vec<bool>> {
+    let mut image = (0..size)
+        .map(|_| (0..size).map(|_| rng.chance(0.3)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let height = MONSTER.len();
+    let width = MONSTER[0].len();
+    let mut occupied = vec![vec![false; size]; size];
+
+    for _ in 0..size * size / 150 {
+        let x = rng.index(size - width + 1);
+        let y = rng.index(size - height + 1);
+
+        let free = (y..y + height).all(|y| (x..x + width).all(|x| !occupied[y][x]));
+
+        if !free {
+            continue;
+        }
+
+        for (dy, row) in MONSTER.iter().enumerate() {
+            for (dx, pixel) in row.bytes().enumerate() {
+                occupied[y + dy][x + dx] = true;
+
+                if pixel == b'#' {
+                    image[y + dy][x + dx] = true;
+                }
+            }
+        }
+    }
+
+    image
+}
+
+fn count_orientations_with_monsters(image: &[Vec<bool>]) -> usize {
+    let mut image = image.to_vec();
+    let mut count = 0;
+
+    for _ in 0..4 {
+        image = rotate(&image);
+        let flipped = image.iter().rev().cloned().collect::<Vec<_>>();
+        count += has_monster(&image) as usize + has_monster(&flipped) as usize;
+    }
+
+    count
+}
+
+fn has_monster(image: &[Vec<bool>]) -> bool {
+    let pixels = MONSTER
+        .iter()
+        .enumerate()
+        .flat_map(|(dy, row)| {
+            row.bytes()
+                .enumerate()
+                .filter(|(_, pixel)| *pixel == b'#')
+                .map(move |(dx, _)| (dx, dy))
+        })
+        .collect::<Vec<_>>();
+
+    let size = image.len();
+    let height = MONSTER.len();
+    let width = MONSTER[0].len();
+
+    (0..=size - height)
+        .any(|y| (0..=size - width).any(|x| pixels.iter().all(|(dx, dy)| image[y + dy][x + dx])))
+}
+
+fn rotate(grid: &[Vec<bool>]) -> Vec<Vec<bool>> {
+    let size = grid.len();
+
+    (0..size)
+        .map(|y| (0..size).map(|x| grid[size - x - 1][y]).collect())
+        .collect()
+}
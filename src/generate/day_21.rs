@@ -0,0 +1,124 @@
+use super::Rng;
+use std::collections::{HashMap, HashSet};
+
+const ALLERGENS: [&str; 8] = [
+    "dairy",
+    "eggs",
+    "fish",
+    "nuts",
+    "peanuts",
+    "sesame",
+    "shellfish",
+    "soy",
+];
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(ALLERGENS.len());
+    let mut names = HashSet::new();
+
+    while names.len() < 20 + size / 2 {
+        let syllables = rng.range(2, 4) as usize;
+        names.insert(rng.word(syllables));
+    }
+
+    let mut ingredients = names.into_iter().collect::<Vec<_>>();
+    ingredients.sort();
+    rng.shuffle(&mut ingredients);
+
+    // The first ingredients contain the allergens, in the same order
+    let allergen_count = ALLERGENS.len();
+    let mut foods = Vec::with_capacity(size);
+
+    while foods.len() < size || !is_solvable(&foods) {
+        foods.push(random_food(rng, &ingredients, allergen_count));
+    }
+
+    let mut output = String::new();
+
+    for (ingredients, allergens) in foods {
+        let allergens = allergens.iter().map(|&i| ALLERGENS[i]).collect::<Vec<_>>();
+        output.push_str(&format!(
+            "{} (contains {})\n",
+            ingredients.join(" "),
+            allergens.join(", ")
+        ));
+    }
+
+    output
+}
+
+fn random_food(
+    rng: &mut Rng,
+    ingredients: &[String],
+    allergen_count: usize,
+) -> (Vec<String>, Vec<usize>) {
+    let mut allergens = (0..rng.range(1, 3))
+        .map(|_| rng.index(allergen_count))
+        .collect::<Vec<_>>();
+    allergens.sort_unstable();
+    allergens.dedup();
+
+    let mut food = allergens
+        .iter()
+        .map(|&i| ingredients[i].clone())
+        .collect::<Vec<_>>();
+
+    for _ in 0..rng.range(4, 12) {
+        let ingredient = rng.choose(ingredients);
+
+        if !food.contains(ingredient) {
+            food.push(ingredient.clone());
+        }
+    }
+
+    rng.shuffle(&mut food);
+    (food, allergens)
+}
+
+// Mirrors the elimination the puzzle relies on, which requires every allergen to be resolved
+fn is_solvable(foods: &[(Vec<String>, Vec<usize>)]) -> bool {
+    let mut candidates = HashMap::<usize, HashSet<&str>>::new();
+
+    for (ingredients, allergens) in foods {
+        let ingredients = ingredients
+            .iter()
+            .map(String::as_str)
+            .collect::<HashSet<_>>();
+
+        for allergen in allergens {
+            candidates
+                .entry(*allergen)
+                .and_modify(|set| set.retain(|x| ingredients.contains(x)))
+                .or_insert_with(|| ingredients.clone());
+        }
+    }
+
+    if candidates.len() < ALLERGENS.len() {
+        return false;
+    }
+
+    let mut known = HashSet::new();
+
+    while !candidates.is_empty() {
+        let solved = candidates
+            .iter()
+            .filter(|(_, set)| set.len() == 1)
+            .map(|(allergen, set)| (*allergen, *set.iter().next().unwrap()))
+            .collect::<Vec<_>>();
+
+        if solved.is_empty() {
+            return false;
+        }
+
+        for (allergen, ingredient) in solved {
+            candidates.remove(&allergen);
+            known.insert(ingredient);
+        }
+
+        for set in candidates.values_mut() {
+            set.retain(|x| !known.contains(x));
+        }
+    }
+
+    true
+}
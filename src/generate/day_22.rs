@@ -0,0 +1,19 @@
+use super::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(2, 127);
+    let mut cards = (1..=2 * size).collect::<Vec<_>>();
+    rng.shuffle(&mut cards);
+
+    let deck = |cards: &[usize]| {
+        cards.iter().fold(String::new(), |output, card| {
+            output + &format!("{}\n", card)
+        })
+    };
+
+    format!(
+        "Player 1:\n{}\nPlayer 2:\n{}",
+        deck(&cards[..size]),
+        deck(&cards[size..])
+    )
+}
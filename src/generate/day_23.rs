@@ -0,0 +1,7 @@
+use super::Rng;
+
+pub fn generate(rng: &mut Rng, _size: usize) -> String {
+    let mut cups = (1..=9).collect::<Vec<_>>();
+    rng.shuffle(&mut cups);
+    cups.iter().map(|cup| cup.to_string()).collect::<String>() + "\n"
+}
@@ -0,0 +1,17 @@
+use super::Rng;
+
+const DIRECTIONS: [&str; 6] = ["e", "se", "sw", "w", "nw", "ne"];
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+
+    for _ in 0..size {
+        for _ in 0..rng.range(1, 20) {
+            output.push_str(DIRECTIONS[rng.index(DIRECTIONS.len())]);
+        }
+
+        output.push('\n');
+    }
+
+    output
+}
@@ -0,0 +1,23 @@
+use super::Rng;
+
+const MODULUS: u64 = 20201227;
+const SUBJECT: u64 = 7;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let max = (size as u64).clamp(2, MODULUS - 2);
+    let card = transform(rng.range(1, max));
+
+    let door = loop {
+        let door = transform(rng.range(1, max));
+
+        if door != card {
+            break door;
+        }
+    };
+
+    format!("{}\n{}\n", card, door)
+}
+
+fn transform(loop_size: u64) -> u64 {
+    (0..loop_size).fold(1, |value, _| value * SUBJECT % MODULUS)
+}
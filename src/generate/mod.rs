@@ -0,0 +1,93 @@
+use crate::challenge::Day;
+use anyhow::Context;
+use rng::Rng;
+
+mod day_01;
+mod day_02;
+mod day_03;
+mod day_04;
+mod day_05;
+mod day_06;
+mod day_07;
+mod day_08;
+mod day_09;
+mod day_10;
+mod day_11;
+mod day_12;
+mod day_13;
+mod day_14;
+mod day_15;
+mod day_16;
+mod day_17;
+mod day_18;
+mod day_19;
+mod day_20;
+mod day_21;
+mod day_22;
+mod day_23;
+mod day_24;
+mod day_25;
+mod rng;
+
+const DEFAULT_SEED: u64 = 0;
+
+// Prints a synthetic input for a day, the same size and seed always give the same input
+pub fn run(mut args: impl Iterator<Item = String>) -> anyhow::Result<()> {
+    const USAGE: &str = "expecting generate <day> <size> [seed]";
+
+    let day = args
+        .next()
+        .with_context(|| format!("Missing day, {}", USAGE))?
+        .parse()?;
+
+    let size = args
+        .next()
+        .with_context(|| format!("Missing size, {}", USAGE))?
+        .parse()
+        .context("Invalid size")?;
+
+    let seed = match args.next() {
+        Some(seed) => seed.parse().context("Invalid seed")?,
+        None => DEFAULT_SEED,
+    };
+
+    print!("{}", generate(day, size, seed));
+    Ok(())
+}
+
+// The size is roughly the number of lines, or whatever else the day's input scales with (e.g. the
+// grid width on day 11 or the number of tiles per side on day 20). Days with a fixed input shape
+// clamp it to what the puzzle allows.
+pub fn generate(day: Day, size: usize, seed: u64) -> String {
+    use Day::*;
+
+    let rng = &mut Rng::new(seed);
+
+    match day {
+        Day01 => day_01::generate(rng, size),
+        Day02 => day_02::generate(rng, size),
+        Day03 => day_03::generate(rng, size),
+        Day04 => day_04::generate(rng, size),
+        Day05 => day_05::generate(rng, size),
+        Day06 => day_06::generate(rng, size),
+        Day07 => day_07::generate(rng, size),
+        Day08 => day_08::generate(rng, size),
+        Day09 => day_09::generate(rng, size),
+        Day10 => day_10::generate(rng, size),
+        Day11 => day_11::generate(rng, size),
+        Day12 => day_12::generate(rng, size),
+        Day13 => day_13::generate(rng, size),
+        Day14 => day_14::generate(rng, size),
+        Day15 => day_15::generate(rng, size),
+        Day16 => day_16::generate(rng, size),
+        Day17 => day_17::generate(rng, size),
+        Day18 => day_18::generate(rng, size),
+        Day19 => day_19::generate(rng, size),
+        Day20 => day_20::generate(rng, size),
+        Day21 => day_21::generate(rng, size),
+        Day22 => day_22::generate(rng, size),
+        Day23 => day_23::generate(rng, size),
+        Day24 => day_24::generate(rng, size),
+        Day25 => day_25::generate(rng, size),
+    }
+}
@@ -0,0 +1,55 @@
+// SplitMix64, which is small and good enough for generating puzzle inputs
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // Returns a value in the inclusive range
+    pub fn range(&mut self, min: u64, max: u64) -> u64 {
+        min + self.next_u64() % (max - min + 1)
+    }
+
+    pub fn index(&mut self, length: usize) -> usize {
+        assert!(length > 0, "Cannot pick an index out of an empty range");
+        self.range(0, length as u64 - 1) as usize
+    }
+
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    pub fn choose<'a, T>(&mut self, values: &'a [T]) -> &'a T {
+        &values[self.index(values.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, values: &mut [T]) {
+        for i in (1..values.len()).rev() {
+            values.swap(i, self.index(i + 1));
+        }
+    }
+
+    // Pronounceable made up word, used for names that need to be unique
+    pub fn word(&mut self, syllables: usize) -> String {
+        const CONSONANTS: &[u8] = b"bcdfghjklmnprstvxz";
+        const VOWELS: &[u8] = b"aeiou";
+
+        let mut word = String::with_capacity(syllables * 2);
+
+        for _ in 0..syllables {
+            word.push(*self.choose(CONSONANTS) as char);
+            word.push(*self.choose(VOWELS) as char);
+        }
+
+        word
+    }
+}
@@ -0,0 +1,275 @@
+use crate::challenge::{Answer, Challenge, Day};
+use anyhow::{anyhow, Context};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const DEFAULT_PATH: &str = "history.tsv";
+const WINDOW_SIZE: usize = 10;
+const MIN_WINDOW_SIZE: usize = 3;
+const SLOWDOWN_FACTOR: f64 = 2.0;
+
+struct Record {
+    timestamp: u64,
+    commit: String,
+    challenge: Challenge,
+    input: String,
+    answer: String,
+    duration: Duration,
+}
+
+impl Record {
+    fn parse(line: &str) -> anyhow::Result<Self> {
+        let mut fields = line.split('\t');
+        let mut next = |name| fields.next().with_context(|| format!("Missing {}", name));
+
+        Ok(Record {
+            timestamp: next("timestamp")?.parse()?,
+            commit: next("commit")?.to_string(),
+            challenge: next("challenge")?.parse()?,
+            input: next("input")?.to_string(),
+            answer: next("answer")?.to_string(),
+            duration: Duration::from_nanos(next("duration")?.parse()?),
+        })
+    }
+}
+
+impl std::fmt::Display for Record {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            self.timestamp,
+            self.commit,
+            self.challenge.code(),
+            self.input,
+            self.answer,
+            self.duration.as_nanos()
+        )
+    }
+}
+
+pub fn record(challenge: &Challenge, input: &str, answer: &Answer) -> anyhow::Result<()> {
+    let record = Record {
+        timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        commit: find_commit().unwrap_or_else(|| "-".to_string()),
+        challenge: *challenge,
+        input: hash(input),
+        answer: answer.value.replace(['\t', '\n'], " "),
+        duration: answer.duration,
+    };
+
+    let path = path();
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Cannot open history file {}", path))?;
+
+    writeln!(file, "{}", record)?;
+    Ok(())
+}
+
+// Prints the duration trend of every challenge (or only the given ones) and flags runs which were
+// significantly slower than the runs before them, as well as answers which changed for an input
+pub fn run(args: impl Iterator<Item = String>) -> anyhow::Result<()> {
+    let filter = args
+        .map(|arg| arg.parse())
+        .collect::<Result<Vec<Challenge>, _>>()?;
+
+    print!("{}", report(&filter)?);
+    Ok(())
+}
+
+// The report of the recorded runs of the given challenges, or of all of them without any
+pub fn report(filter: &[Challenge]) -> anyhow::Result<String> {
+    let mut challenges = BTreeMap::<Challenge, Vec<Record>>::new();
+
+    for record in load()? {
+        if filter.is_empty() || filter.contains(&record.challenge) {
+            challenges.entry(record.challenge).or_default().push(record);
+        }
+    }
+
+    if challenges.is_empty() {
+        return Err(anyhow!("No runs have been recorded in {}", path()));
+    }
+
+    let mut output = String::new();
+
+    for (challenge, records) in &challenges {
+        report_challenge(&mut output, challenge, records)?;
+    }
+
+    Ok(output)
+}
+
+// The answer of the latest run of every challenge on the given input of its day, as answers to
+// other inputs (e.g. generated ones) say nothing about the puzzle input
+pub fn latest_answers(
+    inputs: &BTreeMap<Day, String>,
+) -> anyhow::Result<BTreeMap<Challenge, String>> {
+    let hashes = inputs
+        .iter()
+        .map(|(day, input)| (*day, hash(input)))
+        .collect::<BTreeMap<_, _>>();
+
+    Ok(load()?
+        .into_iter()
+        .filter(|record| hashes.get(&record.challenge.day()) == Some(&record.input))
+        .map(|record| (record.challenge, record.answer))
+        .collect())
+}
+
+fn report_challenge(
+    output: &mut String,
+    challenge: &Challenge,
+    records: &[Record],
+) -> std::fmt::Result {
+    let durations = records
+        .iter()
+        .map(|record| record.duration)
+        .collect::<Vec<_>>();
+
+    let overall = median(&durations);
+    let latest = durations[durations.len() - 1];
+
+    // Coarse timers can measure runs as taking no time at all, which leaves nothing to compare to
+    let change = match ratio(latest, overall) {
+        Some(ratio) => format!("{:+.0}%", (ratio - 1.0) * 100.0),
+        None => "n/a".to_string(),
+    };
+
+    writeln!(
+        output,
+        "{}: {} runs, median = {:?}, latest = {:?} ({})",
+        challenge,
+        records.len(),
+        overall,
+        latest,
+        change
+    )?;
+
+    let mut answers = BTreeMap::<&str, &str>::new();
+
+    for (i, record) in records.iter().enumerate() {
+        let window = &durations[i.saturating_sub(WINDOW_SIZE)..i];
+
+        if window.len() >= MIN_WINDOW_SIZE {
+            let median = median(window);
+
+            if let Some(slowdown) =
+                ratio(record.duration, median).filter(|slowdown| *slowdown >= SLOWDOWN_FACTOR)
+            {
+                writeln!(
+                    output,
+                    "  {} {}: {:?} is {:.1}x slower than the rolling median of {:?}",
+                    format_timestamp(record.timestamp),
+                    record.commit,
+                    record.duration,
+                    slowdown,
+                    median
+                )?;
+            }
+        }
+
+        if let Some(previous) = answers.insert(&record.input, &record.answer) {
+            if previous != record.answer {
+                writeln!(
+                    output,
+                    "  {} {}: answer changed from {} to {} for input {}",
+                    format_timestamp(record.timestamp),
+                    record.commit,
+                    previous,
+                    record.answer,
+                    record.input
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn load() -> anyhow::Result<Vec<Record>> {
+    let path = path();
+
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error).with_context(|| format!("Cannot read {}", path)),
+    };
+
+    content
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            Record::parse(line).with_context(|| format!("Invalid record on line {}", i + 1))
+        })
+        .collect()
+}
+
+fn path() -> String {
+    std::env::var("HISTORY").unwrap_or_else(|_| DEFAULT_PATH.to_string())
+}
+
+fn find_commit() -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    if output.status.success() {
+        Some(String::from_utf8(output.stdout).ok()?.trim().to_string())
+    } else {
+        None
+    }
+}
+
+// FNV-1a, which unlike the std hasher is stable across releases
+fn hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+
+    format!("{:016x}", hash)
+}
+
+fn ratio(duration: Duration, median: Duration) -> Option<f64> {
+    (!median.is_zero()).then(|| duration.as_secs_f64() / median.as_secs_f64())
+}
+
+fn median(durations: &[Duration]) -> Duration {
+    let mut durations = durations.to_vec();
+    durations.sort_unstable();
+    durations[durations.len() / 2]
+}
+
+// Seconds since the epoch as a UTC date and time, e.g. `2020-12-25 10:59:59`
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86400) as i64;
+    let seconds = timestamp % 86400;
+
+    // Converts days since the epoch into a civil date (Howard Hinnant's algorithm)
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
@@ -1,5 +1,6 @@
 use anyhow::Context;
 
+#[tracing::instrument(skip_all)]
 pub async fn get(url: impl reqwest::IntoUrl) -> anyhow::Result<String> {
     let session = std::env::var("SESSION").context("SESSION is not defined")?;
     let cookie = format!("session={}", session);
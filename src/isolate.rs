@@ -0,0 +1,249 @@
+use crate::challenge::{self, Answer, Challenge, Day};
+use crate::history;
+use anyhow::{anyhow, Context};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::os::unix::process::{CommandExt, ExitStatusExt};
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use std::time::Duration;
+
+const CHILD: &str = "--child";
+
+#[derive(Copy, Clone, Debug)]
+pub struct Limits {
+    // In MiB
+    pub memory: u64,
+    // In seconds
+    pub cpu_time: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            memory: 1024,
+            cpu_time: 60,
+        }
+    }
+}
+
+pub enum Outcome {
+    Solved(Answer),
+    Failed(String),
+}
+
+// Runs every challenge in a process of its own, so that a crash or a runaway solution only takes
+// down its own challenge. The inputs are fetched up front and handed over through stdin.
+pub async fn run(mut args: impl Iterator<Item = String>) -> anyhow::Result<()> {
+    const USAGE: &str = "expecting isolate [--memory <MiB>] [--cpu <seconds>] <challenge>...";
+
+    // Only this executable starts a child, always with the child flag first
+    let first = args.next();
+
+    if first.as_deref() == Some(CHILD) {
+        let code = args.next().context("Missing challenge for the child")?;
+        return child(&code.parse()?);
+    }
+
+    let mut args = first.into_iter().chain(args);
+    let mut limits = Limits::default();
+    let mut challenges = Vec::new();
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .with_context(|| format!("Missing value for {}", arg))
+        };
+
+        match arg.as_str() {
+            "--memory" => limits.memory = value()?.parse().context("Invalid memory limit")?,
+            "--cpu" => limits.cpu_time = value()?.parse().context("Invalid CPU time limit")?,
+            _ => challenges.push(arg.parse::<Challenge>()?),
+        }
+    }
+
+    if challenges.is_empty() {
+        return Err(anyhow!("Missing challenge, {}", USAGE));
+    }
+
+    let program = std::env::current_exe().context("Cannot find the current executable")?;
+    let mut inputs = HashMap::<Day, Result<String, String>>::new();
+    let mut failures = 0;
+
+    for challenge in &challenges {
+        let day = challenge.day();
+
+        if let Entry::Vacant(entry) = inputs.entry(day) {
+            let input = challenge::read_input(day, None)
+                .await
+                .map_err(|error| format!("{:#}", error));
+            entry.insert(input);
+        }
+
+        let outcome = match &inputs[&day] {
+            Ok(input) => isolate(&program, challenge, input, limits)?,
+            Err(error) => Outcome::Failed(error.clone()),
+        };
+
+        match outcome {
+            Outcome::Solved(answer) => {
+                println!(
+                    "{}: {} (duration = {:?})",
+                    challenge, answer.value, answer.duration
+                );
+
+                if let Ok(input) = &inputs[&day] {
+                    if let Err(error) = history::record(challenge, input, &answer) {
+                        eprintln!("Warning: {:#}", error);
+                    }
+                }
+            }
+            Outcome::Failed(reason) => {
+                println!("{}: FAILED, {}", challenge, reason);
+                failures += 1;
+            }
+        }
+    }
+
+    if failures > 0 {
+        Err(anyhow!(
+            "{} of {} challenges failed",
+            failures,
+            challenges.len()
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+// Runs a challenge through `program isolate --child`, which has to be this executable
+pub fn isolate(
+    program: &Path,
+    challenge: &Challenge,
+    input: &str,
+    limits: Limits,
+) -> anyhow::Result<Outcome> {
+    let mut command = Command::new(program);
+    command
+        .args(["isolate", CHILD, &challenge.code()])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let memory = limits
+        .memory
+        .checked_mul(1024 * 1024)
+        .with_context(|| format!("The memory limit of {} MiB is too large", limits.memory))?;
+    let cpu_time = limits.cpu_time;
+
+    // Only async-signal-safe calls are allowed between fork and exec, which setrlimit is. The hard
+    // CPU limit is a second later, so that the soft limit's SIGXCPU comes first.
+    unsafe {
+        command.pre_exec(move || {
+            let data = libc::rlimit {
+                rlim_cur: memory,
+                rlim_max: memory,
+            };
+            let cpu = libc::rlimit {
+                rlim_cur: cpu_time,
+                rlim_max: cpu_time + 1,
+            };
+
+            if libc::setrlimit(libc::RLIMIT_DATA, &data) != 0
+                || libc::setrlimit(libc::RLIMIT_CPU, &cpu) != 0
+            {
+                return Err(std::io::Error::last_os_error());
+            }
+
+            Ok(())
+        });
+    }
+
+    let mut child = command
+        .spawn()
+        .with_context(|| format!("Cannot start {}", program.display()))?;
+
+    // A child which dies before reading all of its input closes the pipe, which is reported below
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(input.as_bytes());
+    }
+
+    let output = child.wait_with_output()?;
+    Ok(outcome(&output, limits))
+}
+
+fn outcome(output: &Output, limits: Limits) -> Outcome {
+    if !output.status.success() {
+        return Outcome::Failed(reason(output, limits));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    match stdout.split_once('\n') {
+        Some((nanos, value)) => match nanos.parse() {
+            Ok(nanos) => Outcome::Solved(Answer {
+                value: value.to_string(),
+                duration: Duration::from_nanos(nanos),
+            }),
+            Err(_) => Outcome::Failed(format!("unexpected output {:?}", stdout)),
+        },
+        None => Outcome::Failed(format!("unexpected output {:?}", stdout)),
+    }
+}
+
+// Why the child died, from its signal and from what it wrote to stderr, e.g. a panic message
+fn reason(output: &Output, limits: Limits) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let message = stderr
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("note:"))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let reason = match output.status.signal() {
+        Some(libc::SIGXCPU) => {
+            return format!("exceeded the CPU time limit of {}s", limits.cpu_time)
+        }
+        _ if message.contains("memory allocation of") => {
+            format!("exceeded the memory limit of {} MiB", limits.memory)
+        }
+        Some(signal) => format!("killed by {}", signal_name(signal)),
+        None => output.status.to_string(),
+    };
+
+    if message.is_empty() {
+        reason
+    } else {
+        format!("{}, {}", reason, message)
+    }
+}
+
+// e.g. SIGKILL, which may come from the hard CPU time limit but also from the kernel running out of
+// memory or from another process
+fn signal_name(signal: i32) -> String {
+    let name = match signal {
+        libc::SIGABRT => "SIGABRT",
+        libc::SIGBUS => "SIGBUS",
+        libc::SIGILL => "SIGILL",
+        libc::SIGINT => "SIGINT",
+        libc::SIGKILL => "SIGKILL",
+        libc::SIGSEGV => "SIGSEGV",
+        libc::SIGTERM => "SIGTERM",
+        _ => return format!("signal {}", signal),
+    };
+
+    format!("{} (signal {})", name, signal)
+}
+
+// The answer goes to stdout as the duration in nanoseconds on the first line, then the value
+fn child(challenge: &Challenge) -> anyhow::Result<()> {
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input)?;
+
+    let answer = challenge::run(challenge, &input)?;
+    print!("{}\n{}", answer.duration.as_nanos(), answer.value);
+
+    Ok(())
+}
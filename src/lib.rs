@@ -0,0 +1,16 @@
+pub mod benchmark;
+pub mod boarding;
+pub mod challenge;
+pub mod export;
+pub mod generate;
+pub mod history;
+mod http;
+#[cfg(unix)]
+pub mod isolate;
+pub mod passports;
+pub mod passwords;
+pub mod status;
+pub mod toboggan;
+pub mod trace;
+pub mod validate;
+pub mod visualize;
@@ -1,21 +1,80 @@
+use advent_of_code_2020::{
+    benchmark, boarding, challenge, export, generate, history, passports, passwords, status,
+    toboggan, trace, validate, visualize,
+};
+use anyhow::{anyhow, Context};
 use std::io::BufRead;
-
-mod challenge;
-mod http;
+use std::process::ExitCode;
 
 #[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    if std::env::args().count() > 1 {
-        run(std::env::args().skip(1).map(Ok)).await
-    } else {
-        run(std::io::stdin().lock().lines()).await
+async fn main() -> ExitCode {
+    match cli().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Error: {:?}", error);
+            exit_code(&error)
+        }
+    }
+}
+
+async fn cli() -> anyhow::Result<()> {
+    let mut args = std::env::args().skip(1).peekable();
+    let mut explain = false;
+    let mut trace = None;
+
+    while let Some(option) = args.next_if(|arg| arg.starts_with("--")) {
+        match option.as_str() {
+            "--explain" => explain = true,
+            "--trace" => trace = Some(args.next().context("Missing file for --trace")?),
+            _ => return Err(anyhow!("Unknown option {}", option)),
+        }
+    }
+
+    // Held until the end so that the trace file is complete
+    let _trace = trace.as_deref().map(trace::chrome).transpose()?;
+
+    match args.peek().map(String::as_str) {
+        Some("benchmark") => benchmark::run(args.skip(1)),
+        Some("boarding") => boarding::run(args.skip(1)).await,
+        Some("export") => export::run(args.skip(1)).await,
+        Some("generate") => generate::run(args.skip(1)),
+        Some("history") => history::run(args.skip(1)),
+        #[cfg(unix)]
+        Some("isolate") => advent_of_code_2020::isolate::run(args.skip(1)).await,
+        Some("passports") => passports::run(args.skip(1)).await,
+        Some("passwords") => passwords::run(args.skip(1)).await,
+        Some("status") => status::run(args.skip(1)).await,
+        Some("toboggan") => toboggan::run(args.skip(1)).await,
+        Some("validate") => validate::run(args.skip(1)),
+        Some("visualize") => visualize::run(args.skip(1)).await,
+        Some(_) => run(args.map(Ok), explain).await,
+        None => run(std::io::stdin().lock().lines(), explain).await,
     }
 }
 
-async fn run(args: impl Iterator<Item = std::io::Result<String>>) -> anyhow::Result<()> {
+#[tracing::instrument(skip_all)]
+async fn run(
+    args: impl Iterator<Item = std::io::Result<String>>,
+    explain: bool,
+) -> anyhow::Result<()> {
     for arg in args {
-        challenge::solve(&arg?.parse()?).await?;
+        challenge::solve(&arg?.parse()?, explain).await?;
     }
 
     Ok(())
 }
+
+// Errors of the challenge framework get a code of their own, anything else exits with 1
+fn exit_code(error: &anyhow::Error) -> ExitCode {
+    use challenge::Error::*;
+
+    let code = match error.chain().find_map(|cause| cause.downcast_ref()) {
+        Some(InvalidChallenge(_) | InvalidDay(_) | InvalidPart(_)) => 2,
+        Some(UnknownChallenge(_)) => 3,
+        Some(InputFetch { .. }) => 4,
+        Some(Solver { .. }) => 5,
+        None => 1,
+    };
+
+    ExitCode::from(code)
+}
@@ -0,0 +1,46 @@
+use crate::challenge::{self, Day, Part, PassportSchema, ReportFormat};
+use anyhow::{anyhow, Context};
+
+// Checks the day 4 passports against a schema, either one of the parts' built-in schemas or a
+// schema file, e.g. `passports schemas/passports_b.schema`, so that other documents only need a
+// schema of their own. --report lists why each passport is valid or not, as JSON with --json.
+pub async fn run(mut args: impl Iterator<Item = String>) -> anyhow::Result<()> {
+    const USAGE: &str =
+        "expecting passports [--report] [--json] <a|b|schema file> [--input <file>]";
+
+    let mut schema = None;
+    let mut path = None;
+    let mut format = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--report" => format = format.or(Some(ReportFormat::Text)),
+            "--json" => format = Some(ReportFormat::Json),
+            "--input" => path = Some(args.next().context("Missing value for --input")?),
+            _ if schema.is_none() => schema = Some(arg),
+            _ => return Err(anyhow!("Unexpected argument {}, {}", arg, USAGE)),
+        }
+    }
+
+    let schema = schema.with_context(|| format!("Missing schema, {}", USAGE))?;
+    let file: PassportSchema;
+    let schema = match schema.parse::<Part>() {
+        Ok(part) => PassportSchema::builtin(part),
+        Err(_) => {
+            file = std::fs::read_to_string(&schema)
+                .with_context(|| format!("Cannot read {}", schema))?
+                .parse()
+                .with_context(|| format!("Invalid schema {}", schema))?;
+            &file
+        }
+    };
+
+    let input = challenge::read_input(Day::Day04, path.as_deref()).await?;
+
+    match format {
+        Some(format) => print!("{}", schema.report(&input, format)),
+        None => println!("{} passports are valid", schema.count_valid(&input)),
+    }
+
+    Ok(())
+}
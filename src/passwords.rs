@@ -0,0 +1,34 @@
+use crate::challenge::{self, Day, Parsing};
+use anyhow::{anyhow, Context};
+
+// Checks the day 2 passwords against policies combined with and/or, e.g.
+// `passwords min-length=10 and classes=3 or positions`. Malformed lines are an error unless they
+// are allowed with --lenient, which skips them and lists them after the report.
+pub async fn run(mut args: impl Iterator<Item = String>) -> anyhow::Result<()> {
+    const USAGE: &str =
+        "expecting passwords [--lenient] <policy> [and|or <policy>]... [--input <file>]";
+
+    let mut policy = Vec::new();
+    let mut path = None;
+    let mut parsing = Parsing::Strict;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--lenient" => parsing = Parsing::Lenient,
+            "--input" => path = Some(args.next().context("Missing value for --input")?),
+            _ => policy.push(arg),
+        }
+    }
+
+    if policy.is_empty() {
+        return Err(anyhow!("Missing policy, {}", USAGE));
+    }
+
+    let input = challenge::read_input(Day::Day02, path.as_deref()).await?;
+    print!(
+        "{}",
+        challenge::password_report(&input, &policy.join(" "), parsing)?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,220 @@
+use crate::challenge::{self, Challenge, Day, Part};
+use crate::history;
+use anyhow::anyhow;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+const EVENT_URL: &str = "https://adventofcode.com/2020";
+const DAYS_PER_ROW: usize = 5;
+
+lazy_static! {
+    // e.g. <a aria-label="Day 1, two stars" class="calendar-day1 calendar-verycomplete">
+    static ref CALENDAR_DAY: Regex =
+        Regex::new(r#"class="calendar-day(\d+)(?: calendar-(complete|verycomplete))?""#).unwrap();
+    static ref ACCEPTED_ANSWER: Regex =
+        Regex::new(r"Your puzzle answer was <code>([^<]*)</code>").unwrap();
+}
+
+#[derive(Copy, Clone, Default, Eq, PartialEq, Debug)]
+pub struct Star {
+    // There is a solution for the challenge
+    pub implemented: bool,
+    // The latest answer recorded for the account's input is the one the account had accepted
+    pub verified: bool,
+    pub earned: bool,
+}
+
+impl Star {
+    fn symbol(&self) -> char {
+        match (self.earned, self.implemented, self.verified) {
+            (true, _, true) => '*',
+            (true, true, false) => '+',
+            (true, false, _) => '-',
+            (false, true, _) => 'o',
+            (false, false, _) => '.',
+        }
+    }
+}
+
+// What the account has earned, as shown on the calendar and the pages of the days
+#[derive(Default, Debug)]
+pub struct Account {
+    pub earned: Vec<Challenge>,
+    pub answers: BTreeMap<Challenge, String>,
+    // The puzzle inputs of the days with accepted answers
+    pub inputs: BTreeMap<Day, String>,
+}
+
+// Prints a calendar of which stars are earned and which of them the solutions got right
+pub async fn run(mut args: impl Iterator<Item = String>) -> anyhow::Result<()> {
+    let offline = match args.next().as_deref() {
+        None => false,
+        Some("--offline") => true,
+        Some(arg) => {
+            return Err(anyhow!(
+                "Unknown option {}, expecting status [--offline]",
+                arg
+            ))
+        }
+    };
+
+    let account = if offline {
+        Account::default()
+    } else {
+        fetch_account().await?
+    };
+
+    let history = history::latest_answers(&account.inputs)?;
+    let stars = stars(&account, &history);
+
+    print!("{}", calendar(&stars));
+
+    for (challenge, accepted) in &account.answers {
+        match history.get(challenge) {
+            Some(answer) if answer != accepted => println!(
+                "{}: the latest answer {} differs from the accepted {}",
+                challenge, answer, accepted
+            ),
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+async fn fetch_account() -> anyhow::Result<Account> {
+    let mut account = Account {
+        earned: parse_calendar(&crate::http::get(EVENT_URL).await?),
+        ..Account::default()
+    };
+
+    // Only the pages of days with stars show accepted answers
+    let mut days = account
+        .earned
+        .iter()
+        .map(Challenge::day)
+        .collect::<Vec<_>>();
+    days.dedup();
+
+    for day in days {
+        let url = format!("{}/day/{}", EVENT_URL, u8::from(day));
+        account
+            .answers
+            .extend(parse_answers(day, &crate::http::get(url).await?));
+        account
+            .inputs
+            .insert(day, challenge::read_input(day, None).await?);
+    }
+
+    Ok(account)
+}
+
+// The stars of every day in the calendar, a day with one star has only earned part A
+pub fn parse_calendar(html: &str) -> Vec<Challenge> {
+    let mut earned = Vec::new();
+
+    for captures in CALENDAR_DAY.captures_iter(html) {
+        let Ok(day) = captures[1].parse::<Day>() else {
+            continue;
+        };
+
+        let parts: &[Part] = match captures.get(2).map(|class| class.as_str()) {
+            Some("complete") => &[Part::PartA],
+            Some("verycomplete") => &[Part::PartA, Part::PartB],
+            _ => &[],
+        };
+
+        earned.extend(parts.iter().map(|&part| Challenge::new(day, part)));
+    }
+
+    earned.sort();
+    earned.dedup();
+    earned
+}
+
+// Answers which were accepted for a day, in the order of the parts
+pub fn parse_answers(day: Day, html: &str) -> BTreeMap<Challenge, String> {
+    [Part::PartA, Part::PartB]
+        .into_iter()
+        .zip(ACCEPTED_ANSWER.captures_iter(html))
+        .map(|(part, captures)| (Challenge::new(day, part), captures[1].to_string()))
+        .collect()
+}
+
+pub fn stars(
+    account: &Account,
+    history: &BTreeMap<Challenge, String>,
+) -> BTreeMap<Challenge, Star> {
+    let implemented = challenge::challenges().collect::<Vec<_>>();
+    let last = Challenge::new(Day::Day25, Part::PartB);
+
+    let mut stars = Day::all()
+        .flat_map(|day| [Part::PartA, Part::PartB].map(|part| Challenge::new(day, part)))
+        .filter(|&challenge| challenge != last)
+        .map(|challenge| {
+            let accepted = account.answers.get(&challenge);
+
+            let star = Star {
+                implemented: implemented.contains(&challenge),
+                verified: accepted.is_some() && accepted == history.get(&challenge),
+                earned: account.earned.contains(&challenge),
+            };
+
+            (challenge, star)
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    // The last star has no puzzle, it is given for all the others and so complete along with them
+    let earned = stars.values().all(|star| star.earned);
+    let star = Star {
+        implemented: earned,
+        verified: earned,
+        earned,
+    };
+    stars.insert(last, star);
+
+    stars
+}
+
+pub fn calendar(stars: &BTreeMap<Challenge, Star>) -> String {
+    let mut calendar = String::new();
+    let days = Day::all().collect::<Vec<_>>();
+
+    for row in days.chunks(DAYS_PER_ROW) {
+        let cells = row
+            .iter()
+            .map(|&day| {
+                let symbols = [Part::PartA, Part::PartB]
+                    .map(|part| stars[&Challenge::new(day, part)].symbol())
+                    .iter()
+                    .collect::<String>();
+
+                format!("{:2} {}", u8::from(day), symbols)
+            })
+            .collect::<Vec<_>>();
+
+        writeln!(calendar, "{}", cells.join("   ")).unwrap();
+    }
+
+    let count = |filter: fn(&Star) -> bool| stars.values().filter(|star| filter(star)).count();
+
+    writeln!(
+        calendar,
+        "\n{} of {} stars earned, {} verified, {} implemented",
+        count(|star| star.earned),
+        stars.len(),
+        count(|star| star.earned && star.verified),
+        count(|star| star.implemented)
+    )
+    .unwrap();
+    writeln!(
+        calendar,
+        "* verified, + earned but not verified, - earned without a solution, o solved but not \
+         earned, . neither"
+    )
+    .unwrap();
+
+    calendar
+}
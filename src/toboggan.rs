@@ -0,0 +1,74 @@
+use crate::challenge::{self, Day, Slope, TobogganMap};
+use anyhow::{anyhow, Context};
+
+// Counts the trees of day 3 along any slopes, searches the slopes up to a bound for the fewest and
+// most trees, or draws the path of a slope over the map
+pub async fn run(args: impl Iterator<Item = String>) -> anyhow::Result<()> {
+    const USAGE: &str = "expecting toboggan slopes <right,down>... | search <bound> | \
+                         render <right,down>, followed by [--input <file>]";
+
+    let mut args = args.collect::<Vec<_>>();
+    let mut path = None;
+
+    if let Some(i) = args.iter().position(|arg| arg == "--input") {
+        path = Some(
+            args.get(i + 1)
+                .context("Missing value for --input")?
+                .clone(),
+        );
+        args.drain(i..i + 2);
+    }
+
+    let (command, args) = args
+        .split_first()
+        .with_context(|| format!("Missing command, {}", USAGE))?;
+
+    let input = challenge::read_input(Day::Day03, path.as_deref()).await?;
+    let map: TobogganMap = input.parse()?;
+
+    match command.as_str() {
+        "slopes" => {
+            let slopes = args
+                .iter()
+                .map(|arg| arg.parse())
+                .collect::<anyhow::Result<Vec<Slope>>>()?;
+
+            for &slope in &slopes {
+                println!("{}: {} trees", slope, map.trees(slope));
+            }
+
+            println!("Product: {}", map.product(&slopes)?);
+        }
+        "search" => {
+            let bound = match args {
+                [bound] => bound.parse().context("Invalid bound")?,
+                _ => return Err(anyhow!("Expecting a single bound, {}", USAGE)),
+            };
+
+            let slopes = map.search(bound);
+            let fewest = slopes.iter().map(|&(_, trees)| trees).min();
+            let most = slopes.iter().map(|&(_, trees)| trees).max();
+
+            for (name, trees) in [("Fewest", fewest), ("Most", most)] {
+                let Some(trees) = trees else {
+                    continue;
+                };
+
+                let matching = slopes
+                    .iter()
+                    .filter(|&&(_, count)| count == trees)
+                    .map(|(slope, _)| format!("({})", slope))
+                    .collect::<Vec<_>>();
+
+                println!("{} trees, {}: {}", name, trees, matching.join(", "));
+            }
+        }
+        "render" => match args {
+            [slope] => print!("{}", map.render(slope.parse()?)),
+            _ => return Err(anyhow!("Expecting a single slope, {}", USAGE)),
+        },
+        _ => return Err(anyhow!("Unknown command {}, {}", command, USAGE)),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use anyhow::Context;
+use tracing_chrome::{ChromeLayerBuilder, FlushGuard};
+use tracing_subscriber::prelude::*;
+
+// Records every span into a Chrome trace file, which can be opened in chrome://tracing or Perfetto.
+// The file is only complete once the returned guard is dropped.
+pub fn chrome(path: &str) -> anyhow::Result<FlushGuard> {
+    let file = std::fs::File::create(path).with_context(|| format!("Cannot create {}", path))?;
+    let (layer, guard) = ChromeLayerBuilder::new()
+        .writer(file)
+        .include_args(true)
+        .build();
+
+    tracing::subscriber::set_global_default(tracing_subscriber::registry().with(layer))
+        .context("Cannot install the trace subscriber")?;
+
+    Ok(guard)
+}
@@ -0,0 +1,180 @@
+use crate::challenge::{self, Answer, Challenge};
+use anyhow::{anyhow, Context};
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const INPUT_EXTENSION: &str = "input";
+
+// Runs challenges over a directory of inputs, e.g. one per account, to surface solutions which
+// only hold for some inputs. Each `<name>.input` is checked against `<name>.a` and `<name>.b`.
+pub fn run(mut args: impl Iterator<Item = String>) -> anyhow::Result<()> {
+    let directory = args
+        .next()
+        .context("Missing directory, expecting validate <directory> <challenge>...")?;
+
+    // The default hook would print every panic next to the report
+    let (failures, total) = quietly(|| validate_all(Path::new(&directory), args))?;
+
+    if failures > 0 {
+        Err(anyhow!(
+            "{} of {} inputs failed validation",
+            failures,
+            total
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+// Runs f without reporting its panics. The hook is global, so the previous one is put back once f
+// returns.
+pub fn quietly<R>(f: impl FnOnce() -> R) -> R {
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    let result = f();
+    std::panic::set_hook(hook);
+    result
+}
+
+// Prints the outcome of every input, returning how many of them failed and how many there are
+fn validate_all(
+    directory: &Path,
+    args: impl Iterator<Item = String>,
+) -> anyhow::Result<(usize, usize)> {
+    let mut failures = 0usize;
+    let mut total = 0usize;
+
+    for arg in args {
+        let challenge = arg.parse()?;
+        let outcomes = validate(&challenge, directory)?;
+
+        for (name, outcome) in &outcomes {
+            println!("{} [{}]: {}", challenge, name, outcome);
+        }
+
+        failures += outcomes
+            .iter()
+            .filter(|(_, outcome)| !outcome.passed())
+            .count();
+        total += outcomes.len();
+    }
+
+    Ok((failures, total))
+}
+
+pub enum Outcome {
+    Passed(Duration),
+    Failed { expected: String, actual: String },
+    Errored(anyhow::Error),
+    Panicked(String),
+}
+
+impl Outcome {
+    pub fn passed(&self) -> bool {
+        matches!(self, Outcome::Passed(_))
+    }
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Passed(duration) => write!(f, "ok (duration = {:?})", duration),
+            Outcome::Failed { expected, actual } => {
+                write!(f, "FAILED, expected {} but got {}", expected, actual)
+            }
+            Outcome::Errored(error) => write!(f, "ERROR, {:#}", error),
+            Outcome::Panicked(message) => write!(f, "PANICKED, {}", message),
+        }
+    }
+}
+
+pub fn validate(challenge: &Challenge, directory: &Path) -> anyhow::Result<Vec<(String, Outcome)>> {
+    validate_with(challenge, directory, challenge::run)
+}
+
+// Checks the inputs of the challenge against another solution than the registered one
+pub fn validate_with(
+    challenge: &Challenge,
+    directory: &Path,
+    solve: impl Fn(&Challenge, &str) -> Result<Answer, challenge::Error>,
+) -> anyhow::Result<Vec<(String, Outcome)>> {
+    let inputs = find_inputs(challenge, directory)?;
+
+    if inputs.is_empty() {
+        return Err(anyhow!(
+            "Cannot find any inputs with expected answers for {} in {}",
+            challenge,
+            directory.display()
+        ));
+    }
+
+    Ok(inputs
+        .into_iter()
+        .map(|(name, input, expected)| {
+            let outcome = check(|| solve(challenge, &input), expected);
+            (name, outcome)
+        })
+        .collect())
+}
+
+fn check(solve: impl FnOnce() -> Result<Answer, challenge::Error>, expected: String) -> Outcome {
+    match std::panic::catch_unwind(AssertUnwindSafe(solve)) {
+        Ok(Ok(answer)) if answer.value == expected => Outcome::Passed(answer.duration),
+        Ok(Ok(answer)) => Outcome::Failed {
+            expected,
+            actual: answer.value,
+        },
+        Ok(Err(error)) => Outcome::Errored(error.into()),
+        Err(payload) => Outcome::Panicked(panic_message(payload.as_ref())),
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+fn find_inputs(
+    challenge: &Challenge,
+    directory: &Path,
+) -> anyhow::Result<Vec<(String, String, String)>> {
+    let mut paths = std::fs::read_dir(directory)
+        .with_context(|| format!("Cannot read directory {}", directory.display()))?
+        .map(|entry| Ok(entry?.path()))
+        .collect::<std::io::Result<Vec<PathBuf>>>()?;
+
+    paths.retain(|path| path.extension().is_some_and(|x| x == INPUT_EXTENSION));
+    paths.sort();
+
+    let mut inputs = Vec::with_capacity(paths.len());
+
+    for path in paths {
+        let expected = path.with_extension(challenge.part().letter().to_string());
+
+        if !expected.is_file() {
+            continue;
+        }
+
+        let name = path
+            .file_stem()
+            .context("Input file has no name")?
+            .to_string_lossy()
+            .into_owned();
+
+        let input = std::fs::read_to_string(&path)
+            .with_context(|| format!("Cannot read {}", path.display()))?;
+
+        let expected = std::fs::read_to_string(&expected)
+            .with_context(|| format!("Cannot read {}", expected.display()))?;
+
+        inputs.push((name, input, expected.trim().to_string()));
+    }
+
+    Ok(inputs)
+}
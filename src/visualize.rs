@@ -0,0 +1,276 @@
+use crate::challenge::{self, Cell, Challenge, Grid, Shade, Simulation};
+use anyhow::{anyhow, Context};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::{Color, Stylize};
+use crossterm::{cursor, execute, queue, terminal};
+use std::io::Write;
+use std::ops::RangeInclusive;
+use std::time::{Duration, Instant};
+
+const DEFAULT_SPEED: f64 = 10.0;
+const MIN_SPEED: f64 = 0.25;
+const MAX_SPEED: f64 = 10_000_000.0;
+// Faster speeds run several generations per frame instead
+const MAX_FRAME_RATE: f64 = 30.0;
+
+// Names of the dimensions beyond x and y, the arrow keys pick the slice in the first two
+const AXES: [&str; 2] = ["z", "w"];
+
+const HELP: &str = "space pause, n step, +/- speed, up/down z, left/right w, q quit";
+
+// Plays a simulation day in the terminal
+pub async fn run(mut args: impl Iterator<Item = String>) -> anyhow::Result<()> {
+    const USAGE: &str =
+        "expecting visualize <challenge> [--speed <generations per second>] [--input <file>]";
+
+    let challenge: Challenge = args
+        .next()
+        .with_context(|| format!("Missing challenge, {}", USAGE))?
+        .parse()?;
+
+    let mut speed = DEFAULT_SPEED;
+    let mut path = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .with_context(|| format!("Missing value for {}", arg))
+        };
+
+        match arg.as_str() {
+            "--speed" => speed = value()?.parse().context("Invalid speed")?,
+            "--input" => path = Some(value()?),
+            _ => return Err(anyhow!("Unknown option {}, {}", arg, USAGE)),
+        }
+    }
+
+    let input = challenge::read_input(challenge.day(), path.as_deref()).await?;
+    let simulation = challenge::simulation(&challenge, &input)?;
+    let _screen = Screen::enter()?;
+
+    Player::new(challenge, simulation, speed).play()
+}
+
+// Raw mode on the alternate screen, restored when dropped so that errors leave a usable terminal
+struct Screen;
+
+impl Screen {
+    fn enter() -> anyhow::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(
+            std::io::stdout(),
+            terminal::EnterAlternateScreen,
+            cursor::Hide
+        )?;
+        Ok(Screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(
+            std::io::stdout(),
+            cursor::Show,
+            terminal::LeaveAlternateScreen
+        );
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+struct Player {
+    challenge: Challenge,
+    simulation: Box<dyn Simulation>,
+    slices: Vec<RangeInclusive<isize>>,
+    slice: Vec<isize>,
+    generation: usize,
+    speed: f64,
+    playing: bool,
+    finished: bool,
+}
+
+impl Player {
+    fn new(challenge: Challenge, simulation: Box<dyn Simulation>, speed: f64) -> Self {
+        let slices = simulation.slices();
+        let slice = slices
+            .iter()
+            .map(|range| 0.clamp(*range.start(), *range.end()))
+            .collect();
+
+        Player {
+            challenge,
+            simulation,
+            slices,
+            slice,
+            generation: 0,
+            speed: speed.clamp(MIN_SPEED, MAX_SPEED),
+            playing: true,
+            finished: false,
+        }
+    }
+
+    fn play(&mut self) -> anyhow::Result<()> {
+        let mut next_frame = Instant::now() + self.frame_duration();
+
+        loop {
+            self.draw()?;
+
+            // Waits for a key until the next frame is due, or for as long as it takes when paused
+            let timeout = next_frame.saturating_duration_since(Instant::now());
+            let key = if !self.playing || event::poll(timeout)? {
+                match event::read()? {
+                    Event::Key(key) if key.kind == KeyEventKind::Press => Some(key),
+                    _ => None,
+                }
+            } else {
+                None
+            };
+
+            if let Some(key) = key {
+                let interrupted =
+                    key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+
+                match key.code {
+                    _ if interrupted => return Ok(()),
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char(' ') => {
+                        self.playing = !self.playing && !self.finished;
+                        next_frame = Instant::now() + self.frame_duration();
+                    }
+                    KeyCode::Char('n') if !self.playing => self.step(1),
+                    KeyCode::Char('+') => self.speed = (self.speed * 2.0).min(MAX_SPEED),
+                    KeyCode::Char('-') => self.speed = (self.speed / 2.0).max(MIN_SPEED),
+                    KeyCode::Up => self.move_slice(0, 1),
+                    KeyCode::Down => self.move_slice(0, -1),
+                    KeyCode::Right => self.move_slice(1, 1),
+                    KeyCode::Left => self.move_slice(1, -1),
+                    _ => {}
+                }
+            }
+
+            if self.playing && Instant::now() >= next_frame {
+                self.step(self.generations_per_frame());
+                next_frame = Instant::now() + self.frame_duration();
+            }
+        }
+    }
+
+    fn step(&mut self, generations: usize) {
+        for _ in 0..generations {
+            if !self.simulation.step() {
+                self.finished = true;
+                self.playing = false;
+                break;
+            }
+
+            self.generation += 1;
+        }
+    }
+
+    fn generations_per_frame(&self) -> usize {
+        (self.speed / MAX_FRAME_RATE).ceil().max(1.0) as usize
+    }
+
+    fn frame_duration(&self) -> Duration {
+        Duration::from_secs_f64(self.generations_per_frame() as f64 / self.speed)
+    }
+
+    fn move_slice(&mut self, axis: usize, delta: isize) {
+        if let (Some(value), Some(range)) = (self.slice.get_mut(axis), self.slices.get(axis)) {
+            *value = (*value + delta).clamp(*range.start(), *range.end());
+        }
+    }
+
+    fn draw(&self) -> anyhow::Result<()> {
+        let (columns, rows) = terminal::size()?;
+        let mut lines = Vec::new();
+
+        let state = if self.finished {
+            "finished"
+        } else if self.playing {
+            "playing"
+        } else {
+            "paused"
+        };
+
+        lines.push(format!(
+            "{}, generation {} ({}, {} per second): {}",
+            self.challenge,
+            self.generation,
+            state,
+            self.speed,
+            self.simulation.status()
+        ));
+
+        if !self.slice.is_empty() {
+            let slice = AXES
+                .iter()
+                .zip(&self.slice)
+                .map(|(axis, value)| format!("{} = {}", axis, value))
+                .collect::<Vec<_>>();
+
+            lines.push(slice.join(", "));
+        }
+
+        lines.push(String::new());
+
+        // The grid is cropped around its center when it does not fit, the help goes at the bottom
+        let frame = self.simulation.render(&self.slice);
+        let height = (rows as usize).saturating_sub(lines.len() + 1);
+        lines.extend(crop(&frame, columns as usize, height));
+
+        let mut stdout = std::io::stdout().lock();
+
+        for (row, line) in lines.iter().enumerate() {
+            queue!(
+                stdout,
+                cursor::MoveTo(0, row as u16),
+                crossterm::style::Print(line),
+                terminal::Clear(terminal::ClearType::UntilNewLine)
+            )?;
+        }
+
+        queue!(
+            stdout,
+            terminal::Clear(terminal::ClearType::FromCursorDown),
+            cursor::MoveTo(0, rows.saturating_sub(1)),
+            crossterm::style::Print(HELP.dark_grey())
+        )?;
+
+        stdout.flush()?;
+        Ok(())
+    }
+}
+
+// Rows of the frame which fit the given size, with the colors of their cells
+fn crop(frame: &Grid<Cell>, width: usize, height: usize) -> Vec<String> {
+    let x = frame.width().saturating_sub(width) / 2;
+    let y = frame.height().saturating_sub(height) / 2;
+
+    frame
+        .rows()
+        .skip(y)
+        .take(height)
+        .map(|row| {
+            let row = &row[x..(x + width).min(row.len())];
+            let mut line = String::new();
+
+            for run in row.chunk_by(|a, b| a.shade == b.shade) {
+                let text = run.iter().map(|cell| cell.symbol).collect::<String>();
+                line.push_str(&text.with(color(run[0].shade)).to_string());
+            }
+
+            line
+        })
+        .collect()
+}
+
+fn color(shade: Shade) -> Color {
+    match shade {
+        Shade::Background => Color::DarkGrey,
+        Shade::Inactive => Color::Grey,
+        Shade::Active => Color::DarkGreen,
+        Shade::Activated => Color::Green,
+        Shade::Deactivated => Color::Red,
+        Shade::Highlight => Color::Yellow,
+    }
+}
@@ -0,0 +1,157 @@
+use advent_of_code_2020::challenge::{self, Challenge, Error, Plane};
+use proptest::prelude::*;
+
+#[test]
+fn example_passes() {
+    let plane = Plane::default();
+
+    for (pass, row, column, id) in [
+        ("FBFBBFFRLR", 44, 5, 357),
+        ("BFFFBBFRRR", 70, 7, 567),
+        ("FFFBBBFRRR", 14, 7, 119),
+        ("BBFFBBFRLL", 102, 4, 820),
+    ] {
+        let seat = plane.decode(pass).unwrap();
+
+        assert_eq!((seat.row(), seat.column(), seat.id()), (row, column, id));
+        assert_eq!(plane.seat(row, column).unwrap().pass(), pass);
+        assert_eq!(plane.seat_with_id(id).unwrap().pass(), pass);
+    }
+}
+
+#[test]
+fn planes_can_have_other_sizes() {
+    let plane = Plane::new(2, 4).unwrap();
+    let seat = plane.decode("BFRLLR").unwrap();
+
+    assert_eq!((plane.rows(), plane.columns()), (4, 16));
+    assert_eq!((seat.row(), seat.column(), seat.id()), (2, 9, 41));
+    assert!(plane.decode("FBFBBFFRLR").is_err());
+
+    assert!(Plane::new(0, 3).is_err());
+    assert!(Plane::new(7, 0).is_err());
+    assert!(Plane::new(30, 3).is_err());
+    assert!(Plane::new(29, 3).is_ok());
+}
+
+#[test]
+fn passes_are_checked() {
+    let plane = Plane::default();
+
+    for (pass, error) in [
+        ("FBFBBFFRL", "Expected 10 characters, found 9"),
+        ("FBFBBFFRLRR", "Expected 10 characters, found 11"),
+        (
+            "FBFBBFFRLr",
+            "Invalid character 'r' at position 10, expecting L or R",
+        ),
+        (
+            "FBFBBFRRLR",
+            "Invalid character 'R' at position 7, expecting F or B",
+        ),
+        (
+            "LBFBBFFRLR",
+            "Invalid character 'L' at position 1, expecting F or B",
+        ),
+    ] {
+        assert_eq!(plane.decode(pass).unwrap_err().to_string(), error);
+    }
+
+    assert!(plane.seat(128, 0).is_err());
+    assert!(plane.seat(0, 8).is_err());
+    assert!(plane.seat_with_id(1024).is_err());
+}
+
+#[test]
+fn parts_reject_bad_passes() {
+    let challenge: Challenge = "05a".parse().unwrap();
+
+    match challenge::run(&challenge, "FBFBBFFRLR\nFBFBBFFRLX\n") {
+        Err(Error::Solver { source, .. }) => assert_eq!(
+            format!("{:#}", source),
+            "Invalid boarding pass on line 2: Invalid character 'X' at position 10, expecting L \
+             or R"
+        ),
+        _ => panic!("a bad pass should fail"),
+    }
+}
+
+#[test]
+fn analysis_shows_every_empty_seat_and_duplicate() {
+    let plane = Plane::new(2, 2).unwrap();
+    let report = plane
+        .analyze("BFLR\nFFLL\nBFLR\nFFRR\nFBLL\nFBRL\nBFLR\nFFLR")
+        .unwrap();
+
+    assert_eq!(
+        report.lines().collect::<Vec<_>>(),
+        [
+            "Seat map, # for a taken seat, 2-9 for a seat on that many passes, . for an empty seat",
+            "0 ##.# 3/4",
+            "1 #.#. 2/4",
+            "2 .3.. 1/4",
+            "3 .... 0/4",
+            "",
+            "6 of 16 seats taken",
+            "Empty seat IDs: 2, 5, 7-8, 10-15",
+            "Empty seats between two taken ones: FFRL (row 0, column 2, seat ID 2), FBLR (row 1, \
+             column 1, seat ID 5)",
+            "Duplicate passes: BFLR (row 2, column 1, seat ID 9) on lines 1, 3, 7",
+        ]
+    );
+}
+
+#[test]
+fn large_planes_only_map_rows_with_passes() {
+    let plane = Plane::new(24, 2).unwrap();
+    let passes = ["FFFFFFFFFFFFFFFFFFFFFFFFLR", "BBBBBBBBBBBBBBBBBBBBBBBBLL"];
+    let report = plane.analyze(&passes.join("\n")).unwrap();
+    let lines = report.lines().collect::<Vec<_>>();
+
+    assert!(lines[0].starts_with("Seat map of the rows with passes"));
+    assert_eq!(lines[1], "       0 .#.. 1/4");
+    assert_eq!(lines[2], "16777215 #... 1/4");
+    assert_eq!(lines[4], "2 of 67108864 seats taken");
+    assert_eq!(lines[5], "Empty seat IDs: 0, 2-67108859, 67108861-67108863");
+}
+
+#[test]
+fn wide_planes_have_no_map() {
+    let plane = Plane::new(4, 28).unwrap();
+    let pass = format!("BFFF{}", "R".repeat(28));
+    let report = plane.analyze(&pass).unwrap();
+    let lines = report.lines().collect::<Vec<_>>();
+
+    assert_eq!(
+        lines[0],
+        "No seat map, rows of 268435456 seats are too wide to show"
+    );
+    assert_eq!(lines[2], "1 of 4294967296 seats taken");
+    assert_eq!(
+        lines[3],
+        "Empty seat IDs: 0-2415919102, 2415919104-4294967295"
+    );
+}
+
+#[test]
+fn analysis_rejects_bad_passes() {
+    let error = Plane::default().analyze("FBFBBFFRLR\n\n").unwrap_err();
+    assert_eq!(
+        format!("{:#}", error),
+        "Invalid boarding pass on line 2: Expected 10 characters, found 0"
+    );
+}
+
+proptest! {
+    #[test]
+    fn passes_round_trip(row_bits in 1u32..16, column_bits in 1u32..16, id: u32) {
+        let plane = Plane::new(row_bits, column_bits).unwrap();
+        let id = id % (plane.rows() * plane.columns());
+        let seat = plane.seat_with_id(id).unwrap();
+        let decoded = plane.decode(&seat.pass()).unwrap();
+
+        prop_assert_eq!(decoded, seat);
+        prop_assert_eq!(plane.seat(seat.row(), seat.column()).unwrap(), seat);
+        prop_assert_eq!(seat.id(), seat.row() * plane.columns() + seat.column());
+    }
+}
@@ -0,0 +1,11 @@
+use advent_of_code_2020::challenge::Challenge;
+use std::path::PathBuf;
+
+// Fixtures of a day live in tests/fixtures/day_NN, where each `<name>.input` comes with the
+// expected answers in `<name>.a` and `<name>.b`
+pub fn fixture_directory(challenge: &Challenge) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(format!("day_{:02}", u8::from(challenge.day())))
+}
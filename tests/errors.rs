@@ -0,0 +1,87 @@
+use advent_of_code_2020::challenge::{self, Challenge, Day, Error, Part};
+use advent_of_code_2020::generate;
+use std::process::Command;
+
+#[test]
+fn invalid_names_are_told_apart() {
+    assert!(matches!(
+        "day".parse::<Challenge>(),
+        Err(Error::InvalidChallenge(_))
+    ));
+    assert!(matches!("26a".parse::<Challenge>(), Err(Error::InvalidDay(day)) if day == "26"));
+    assert!(matches!("0".parse::<Day>(), Err(Error::InvalidDay(_))));
+    assert!(matches!("c".parse::<Part>(), Err(Error::InvalidPart(_))));
+}
+
+#[test]
+fn run_errors_carry_the_challenge() {
+    let missing = Challenge::new(Day::Day25, Part::PartB);
+    assert!(matches!(
+        challenge::run(&missing, ""),
+        Err(Error::UnknownChallenge(challenge)) if challenge == missing
+    ));
+
+    let failing = Challenge::new(Day::Day01, Part::PartA);
+    match challenge::run(&failing, "1\n2\n3") {
+        Err(Error::Solver { challenge, source }) => {
+            assert_eq!(challenge, failing);
+            assert!(!source.to_string().is_empty());
+        }
+        _ => panic!("{} should fail without a matching pair", failing),
+    }
+}
+
+#[test]
+fn seats_which_never_settle_are_an_error() {
+    let challenge = Challenge::new(Day::Day11, Part::PartA);
+
+    match challenge::run(&challenge, ".LL.\nLLLL\nLLLL\n.LL.") {
+        Err(Error::Solver { source, .. }) => {
+            assert_eq!(source.to_string(), "The seats never settle")
+        }
+        _ => panic!("{} should not settle", challenge),
+    }
+}
+
+// Changing any of the jumps or the nop leads into one of the other loops
+#[test]
+fn programs_without_a_fix_are_an_error() {
+    let challenge = Challenge::new(Day::Day08, Part::PartB);
+
+    match challenge::run(&challenge, "nop +0\njmp +2\njmp +0\njmp -3\njmp +0") {
+        Err(Error::Solver { source, .. }) => assert_eq!(
+            source.to_string(),
+            "Cannot find an instruction which fixes the loop"
+        ),
+        _ => panic!("{} should not find a fix", challenge),
+    }
+}
+
+// Six tiles leave a rectangle at best, which used to be drawn past the edge of the image
+#[test]
+fn tiles_which_cannot_form_a_square_are_an_error() {
+    let challenge = Challenge::new(Day::Day20, Part::PartB);
+    let input = generate::generate(Day::Day20, 3, 1);
+    let tiles = input.trim_end().split("\n\n").take(6);
+    let input = tiles.collect::<Vec<_>>().join("\n\n");
+
+    match challenge::run(&challenge, &input) {
+        Err(Error::Solver { source, .. }) => assert_eq!(
+            source.to_string(),
+            "Cannot arrange 6 tiles into a square image"
+        ),
+        _ => panic!("{} should not fit 6 tiles", challenge),
+    }
+}
+
+#[test]
+fn exit_codes_follow_the_error() {
+    for (arg, code) in [("01x", 2), ("26a", 2), ("25b", 3)] {
+        let status = Command::new(env!("CARGO_BIN_EXE_advent-of-code-2020"))
+            .arg(arg)
+            .status()
+            .unwrap();
+
+        assert_eq!(status.code(), Some(code), "{}", arg);
+    }
+}
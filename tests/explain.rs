@@ -0,0 +1,93 @@
+use advent_of_code_2020::challenge::{self, Challenge, Day, Part};
+use common::fixture_directory;
+
+mod common;
+
+#[test]
+fn explanations_cover_fixtures() {
+    for day in [Day::Day01, Day::Day09, Day::Day16, Day::Day20, Day::Day21] {
+        let directory = fixture_directory(&Challenge::new(day, Part::PartA));
+
+        for entry in std::fs::read_dir(&directory).unwrap() {
+            let path = entry.unwrap().path();
+
+            if path.extension().is_some_and(|x| x == "input") {
+                let input = std::fs::read_to_string(&path).unwrap();
+
+                for part in [Part::PartA, Part::PartB] {
+                    let challenge = Challenge::new(day, part);
+                    let explanation = challenge::explain(&challenge, &input);
+
+                    assert!(
+                        matches!(explanation, Ok(Some(_))),
+                        "{} cannot explain {}",
+                        challenge,
+                        path.display()
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn allergens_are_explained() {
+    let directory = fixture_directory(&Challenge::new(Day::Day21, Part::PartA));
+    let input = std::fs::read_to_string(directory.join("example.input")).unwrap();
+
+    assert_eq!(
+        challenge::explain(&challenge("21a"), &input)
+            .unwrap()
+            .unwrap(),
+        "dairy: mxmxvkd\n\
+         fish: sqjhc\n\
+         soy: fvjkl\n\
+         4 ingredients cannot contain any allergen, appearing 5 times\n"
+    );
+}
+
+#[test]
+fn expense_report_is_audited() {
+    let directory = fixture_directory(&Challenge::new(Day::Day01, Part::PartA));
+    let input = std::fs::read_to_string(directory.join("example.input")).unwrap();
+
+    assert_eq!(
+        challenge::explain(&challenge("01a"), &input)
+            .unwrap()
+            .unwrap(),
+        "Lines 1 and 4: 1721 + 299 = 2020, product 514579\n\
+         1 pair adds up to 2020\n"
+    );
+    assert_eq!(
+        challenge::explain(&challenge("01b"), &input)
+            .unwrap()
+            .unwrap(),
+        "Lines 2, 3 and 5: 979 + 366 + 675 = 2020, product 241861950\n\
+         1 triple adds up to 2020\n"
+    );
+}
+
+#[test]
+fn ambiguous_expense_reports() {
+    let input = "1010\n1\n1010\n1010";
+    let pairs = challenge::explain(&challenge("01a"), input)
+        .unwrap()
+        .unwrap();
+    let triples = challenge::explain(&challenge("01b"), input)
+        .unwrap()
+        .unwrap();
+
+    assert!(pairs.contains("Lines 1 and 3: 1010 + 1010 = 2020"));
+    assert!(pairs.contains("3 pairs add up to 2020 with 1 distinct product,"));
+    assert!(!pairs.contains("triple"));
+    assert_eq!(triples, "No triple adds up to 2020, there is no answer\n");
+}
+
+#[test]
+fn days_without_explanation() {
+    assert!(challenge::explain(&challenge("25a"), "").unwrap().is_none());
+}
+
+fn challenge(code: &str) -> Challenge {
+    code.parse().unwrap()
+}
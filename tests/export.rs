@@ -0,0 +1,98 @@
+use advent_of_code_2020::challenge::{self, Challenge, Day, Part};
+use advent_of_code_2020::export::{self, Format};
+use advent_of_code_2020::generate;
+use common::fixture_directory;
+use std::path::PathBuf;
+
+mod common;
+
+#[test]
+fn picture_is_written_as_ppm() {
+    let directory = output_directory("ppm");
+    let input = std::fs::read_to_string(
+        fixture_directory(&Challenge::new(Day::Day20, Part::PartA)).join("generated.input"),
+    )
+    .unwrap();
+
+    let picture = challenge::picture(Day::Day20, &input).unwrap();
+    let path = directory.join("20a.ppm");
+    export::write_image(&picture, 2, Format::Ppm, &path).unwrap();
+
+    let (width, height) = (picture.width() * 2, picture.height() * 2);
+    let header = format!("P6\n{} {}\n255\n", width, height);
+    let bytes = std::fs::read(&path).unwrap();
+
+    assert!(bytes.starts_with(header.as_bytes()));
+    assert_eq!(bytes.len(), header.len() + width * height * 3);
+}
+
+#[test]
+fn picture_is_written_as_png() {
+    let directory = output_directory("png");
+    let input = generate::generate(Day::Day20, 3, 1);
+
+    let picture = challenge::picture(Day::Day20, &input).unwrap();
+    let path = directory.join("20a.png");
+    export::write_image(&picture, 1, Format::Png, &path).unwrap();
+
+    let bytes = std::fs::read(&path).unwrap();
+    assert!(bytes.starts_with(b"\x89PNG\r\n\x1a\n"));
+}
+
+#[test]
+fn every_generation_and_slice_is_a_frame() {
+    for code in ["11a", "17a", "17b", "24b"] {
+        let directory = output_directory(code);
+        let challenge: Challenge = code.parse().unwrap();
+        let input = generate::generate(challenge.day(), 8, 2);
+
+        let mut simulation = challenge::simulation(&challenge, &input).unwrap();
+        let slices = challenge::every_slice(&simulation.slices()).len();
+        let count =
+            export::write_frames(simulation.as_mut(), &directory, code, 1, Format::Ppm, 1000)
+                .unwrap();
+
+        let files = std::fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        let first_generation = files
+            .iter()
+            .filter(|file| file.starts_with(&format!("{}_0000", code)))
+            .count();
+
+        assert_eq!(files.len(), count, "{}", code);
+        assert_eq!(first_generation, slices, "{}", code);
+        assert_eq!(count % slices, 0, "{}", code);
+    }
+}
+
+// The seats flip between empty and occupied forever
+#[test]
+fn frames_stop_at_the_limit() {
+    let directory = output_directory("limit");
+    let challenge = Challenge::new(Day::Day11, Part::PartA);
+
+    let mut simulation = challenge::simulation(&challenge, ".LL.\nLLLL\nLLLL\n.LL.").unwrap();
+    let count =
+        export::write_frames(simulation.as_mut(), &directory, "11a", 1, Format::Ppm, 5).unwrap();
+
+    assert_eq!(count, 5);
+    assert_eq!(std::fs::read_dir(&directory).unwrap().count(), 5);
+}
+
+#[test]
+fn other_days_have_no_picture() {
+    assert!(challenge::picture(Day::Day11, "").is_err());
+}
+
+// An empty directory for the images of one test
+fn output_directory(name: &str) -> PathBuf {
+    let directory = PathBuf::from(env!("CARGO_TARGET_TMPDIR"))
+        .join("export")
+        .join(name);
+
+    let _ = std::fs::remove_dir_all(&directory);
+    std::fs::create_dir_all(&directory).unwrap();
+    directory
+}
@@ -0,0 +1 @@
+241861950
@@ -0,0 +1 @@
+336
@@ -0,0 +1 @@
+403
@@ -0,0 +1 @@
+126
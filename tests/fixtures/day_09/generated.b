@@ -0,0 +1 @@
+76
@@ -0,0 +1 @@
+286
@@ -0,0 +1 @@
+136710661187370553612771524
@@ -0,0 +1 @@
+208
@@ -0,0 +1 @@
+175594
@@ -0,0 +1 @@
+362
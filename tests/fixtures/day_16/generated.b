@@ -0,0 +1 @@
+268944202800000
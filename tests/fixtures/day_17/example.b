@@ -0,0 +1 @@
+848
@@ -0,0 +1 @@
+694173
@@ -0,0 +1 @@
+153
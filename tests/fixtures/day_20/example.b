@@ -0,0 +1 @@
+273
@@ -0,0 +1 @@
+308
@@ -0,0 +1 @@
+mxmxvkd,sqjhc,fvjkl
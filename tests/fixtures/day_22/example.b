@@ -0,0 +1 @@
+291
@@ -0,0 +1 @@
+149245887792
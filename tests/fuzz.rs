@@ -0,0 +1,161 @@
+use advent_of_code_2020::challenge::{self, Challenge};
+use common::fixture_directory;
+use proptest::prelude::*;
+use proptest::test_runner::{Config, TestCaseError, TestRunner};
+use std::cell::Cell;
+use std::sync::mpsc;
+use std::time::Duration;
+
+mod common;
+
+// Kept low as some solutions take seconds in debug builds, set PROPTEST_CASES for a longer run
+const CASES: u32 = 32;
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+// Characters which show up in the puzzle inputs, so edits are likely to reach past the first check
+const ALPHABET: &str = "0123456789abcxyzLFBR#.-+*()[]:,=| \n";
+
+// Solutions may return an error for malformed input, but they must neither panic nor hang
+#[test]
+fn solutions_survive_arbitrary_text() {
+    let text = prop_oneof![
+        any::<String>(),
+        proptest::string::string_regex("[0-9a-zA-Z#.\\-+*()\\[\\]:,=| \n]{0,200}").unwrap(),
+    ];
+
+    assert_survives(|_| text.clone().boxed());
+}
+
+#[test]
+fn solutions_survive_edited_fixtures() {
+    assert_survives(|challenge| {
+        let inputs = fixture_inputs(challenge);
+        (proptest::sample::select(inputs), edits())
+            .prop_map(|(input, edits)| apply(&input, &edits))
+            .boxed()
+    });
+}
+
+fn assert_survives(strategy: impl Fn(&Challenge) -> BoxedStrategy<String>) {
+    let failures = challenge::challenges()
+        .filter_map(|challenge| {
+            let cases = std::env::var("PROPTEST_CASES")
+                .ok()
+                .and_then(|cases| cases.parse().ok())
+                .unwrap_or(CASES);
+
+            let config = Config {
+                cases,
+                failure_persistence: None,
+                ..Config::default()
+            };
+
+            let hung = Cell::new(false);
+
+            TestRunner::new(config)
+                .run(&strategy(&challenge), |input| {
+                    survive(challenge, input, &hung)
+                })
+                .err()
+                .map(|error| format!("{}: {}", challenge, error))
+        })
+        .collect::<Vec<_>>();
+
+    assert!(
+        failures.is_empty(),
+        "{} solutions failed:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+// Panics are caught on the solution's own thread, where the test harness captures their message.
+// A solution which hangs cannot be stopped though, so after the first one any further cases of
+// the challenge are rejected. Shrinking then keeps the input which hung, and only a single thread
+// is left behind until the tests exit.
+fn survive(challenge: Challenge, input: String, hung: &Cell<bool>) -> Result<(), TestCaseError> {
+    if hung.get() {
+        return Err(TestCaseError::reject("a previous case did not finish"));
+    }
+
+    let (sender, receiver) = mpsc::channel();
+
+    let worker = std::thread::spawn(move || {
+        let result = std::panic::catch_unwind(|| challenge::run(&challenge, &input).is_ok());
+        let _ = sender.send(result.is_ok());
+    });
+
+    let result = receiver.recv_timeout(TIMEOUT);
+
+    if result.is_ok() {
+        let _ = worker.join();
+    }
+
+    match result {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(TestCaseError::fail("panicked")),
+        Err(_) => {
+            hung.set(true);
+            Err(TestCaseError::fail(format!(
+                "did not finish within {:?}",
+                TIMEOUT
+            )))
+        }
+    }
+}
+
+fn fixture_inputs(challenge: &Challenge) -> Vec<String> {
+    let directory = fixture_directory(challenge);
+
+    let mut inputs = std::fs::read_dir(&directory)
+        .unwrap_or_else(|error| panic!("Cannot read {}: {}", directory.display(), error))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|x| x == "input"))
+        .map(|path| std::fs::read_to_string(path).unwrap())
+        .collect::<Vec<_>>();
+
+    inputs.sort();
+    inputs
+}
+
+#[derive(Clone, Debug)]
+enum Edit {
+    Insert(usize, char),
+    Replace(usize, char),
+    Delete(usize),
+    Truncate(usize),
+}
+
+fn edits() -> impl Strategy<Value = Vec<Edit>> {
+    let character = proptest::sample::select(ALPHABET.chars().collect::<Vec<_>>());
+    let position = any::<usize>();
+
+    let edit = prop_oneof![
+        4 => (position, character.clone()).prop_map(|(at, c)| Edit::Insert(at, c)),
+        4 => (position, character).prop_map(|(at, c)| Edit::Replace(at, c)),
+        4 => position.prop_map(Edit::Delete),
+        1 => position.prop_map(Edit::Truncate),
+    ];
+
+    proptest::collection::vec(edit, 1..8)
+}
+
+fn apply(input: &str, edits: &[Edit]) -> String {
+    let mut chars = input.chars().collect::<Vec<_>>();
+
+    for edit in edits {
+        let len = chars.len();
+
+        match *edit {
+            Edit::Insert(at, c) => chars.insert(at % (len + 1), c),
+            Edit::Replace(at, c) if len > 0 => chars[at % len] = c,
+            Edit::Delete(at) if len > 0 => {
+                chars.remove(at % len);
+            }
+            Edit::Truncate(at) => chars.truncate(at % (len + 1)),
+            _ => {}
+        }
+    }
+
+    chars.into_iter().collect()
+}
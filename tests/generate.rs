@@ -0,0 +1,184 @@
+use advent_of_code_2020::challenge::{self, Challenge, Day, Grid, KSum, Part};
+use advent_of_code_2020::generate;
+use std::collections::HashSet;
+
+// The smallest input each day allows and a typical one, as days 15 and 23 take seconds per run
+const INPUTS: [(usize, u64); 2] = [(1, 0), (40, 1)];
+const SEEDS: u64 = 8;
+
+const MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+#[test]
+fn generated_inputs_are_solvable() {
+    let failures = challenge::challenges()
+        .flat_map(|challenge| check(&challenge))
+        .collect::<Vec<_>>();
+
+    assert!(
+        failures.is_empty(),
+        "{} generated inputs failed:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn generation_is_deterministic() {
+    for challenge in challenge::challenges() {
+        let day = challenge.day();
+        assert_eq!(
+            generate::generate(day, 40, 7),
+            generate::generate(day, 40, 7)
+        );
+    }
+}
+
+// Otherwise the answer would depend on which combination is found first
+#[test]
+fn day_01_has_a_single_pair_and_triple() {
+    for seed in 0..SEEDS {
+        let input = generate::generate(Day::Day01, 40, seed);
+        let values = input
+            .lines()
+            .map(|line| line.parse().unwrap())
+            .collect::<Vec<u64>>();
+
+        assert_eq!(KSum::new(2, 2020).all(&values).len(), 1, "seed {}", seed);
+        assert_eq!(KSum::new(3, 2020).all(&values).len(), 1, "seed {}", seed);
+    }
+}
+
+// Past the largest offset the puzzle's values allow, in case the fix has to jump that far
+#[test]
+fn day_08_offsets_fit_the_puzzle_values() {
+    let input = generate::generate(Day::Day08, 40_000, 0);
+    let challenge = Challenge::new(Day::Day08, Part::PartA);
+
+    assert_eq!(input.lines().count(), i16::MAX as usize);
+    assert!(challenge::run(&challenge, &input).is_ok(), "{}", challenge);
+}
+
+#[test]
+fn day_11_layouts_settle() {
+    for seed in 0..SEEDS {
+        let input = generate::generate(Day::Day11, 90, seed);
+
+        for part in [Part::PartA, Part::PartB] {
+            let challenge = Challenge::new(Day::Day11, part);
+            assert!(
+                challenge::run(&challenge, &input).is_ok(),
+                "{} [seed {}]",
+                challenge,
+                seed
+            );
+        }
+    }
+}
+
+#[test]
+fn day_20_has_a_corner_as_the_top_left_tile() {
+    for (size, seed) in sizes_and_seeds() {
+        let input = generate::generate(Day::Day20, size, seed);
+        let tiles = input
+            .trim_end()
+            .split("\n\n")
+            .map(|tile| tile.lines().skip(1).map(str::as_bytes).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        // Every edge read in both directions, so flipped tiles match as well
+        let edges = |tile: &[&[u8]]| {
+            let top = tile[0].to_vec();
+            let bottom = tile[tile.len() - 1].to_vec();
+            let left = tile.iter().map(|row| row[0]).collect::<Vec<_>>();
+            let right = tile
+                .iter()
+                .map(|row| row[row.len() - 1])
+                .collect::<Vec<_>>();
+
+            [top, bottom, left, right].into_iter().flat_map(|edge| {
+                let reversed = edge.iter().rev().copied().collect::<Vec<_>>();
+                [edge, reversed]
+            })
+        };
+
+        let is_top_left = |index: usize| {
+            let tile = &tiles[index];
+            let others = tiles
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != index)
+                .flat_map(|(_, tile)| edges(tile))
+                .collect::<HashSet<_>>();
+
+            let top = tile[0].to_vec();
+            let left = tile.iter().map(|row| row[0]).collect::<Vec<_>>();
+            !others.contains(&top) && !others.contains(&left)
+        };
+
+        assert!(
+            (0..tiles.len()).any(is_top_left),
+            "size {}, seed {}",
+            size,
+            seed
+        );
+    }
+}
+
+#[test]
+fn day_20_monsters_show_in_a_single_orientation() {
+    let monster = Grid::parse(&MONSTER, |pixel| pixel == b'#').unwrap();
+
+    for (size, seed) in sizes_and_seeds() {
+        let input = generate::generate(Day::Day20, size, seed);
+        let picture = challenge::picture(Day::Day20, &input).unwrap();
+        let pixels = picture.cells().iter().map(|cell| cell.symbol != '.');
+        let image = Grid::new(picture.width(), picture.height(), pixels.collect());
+
+        let orientations = monster
+            .orientations()
+            .filter(|monster| has_monster(&image, monster))
+            .count();
+
+        assert_eq!(orientations, 1, "size {}, seed {}", size, seed);
+    }
+}
+
+fn check(challenge: &Challenge) -> Vec<String> {
+    let mut failures = Vec::new();
+
+    for (size, seed) in INPUTS {
+        let input = generate::generate(challenge.day(), size, seed);
+
+        if let Err(error) = challenge::run(challenge, &input) {
+            failures.push(format!(
+                "{} [size {}, seed {}]: {:#}",
+                challenge, size, seed, error
+            ));
+        }
+    }
+
+    failures
+}
+
+// The smallest and the largest day 20 puzzle
+fn sizes_and_seeds() -> impl Iterator<Item = (usize, u64)> {
+    [3, 12]
+        .into_iter()
+        .flat_map(|size| (0..SEEDS).map(move |seed| (size, seed)))
+}
+
+fn has_monster(image: &Grid<bool>, monster: &Grid<bool>) -> bool {
+    let pixels = monster
+        .positions()
+        .filter(|position| monster[*position])
+        .collect::<Vec<_>>();
+
+    (0..=image.height() - monster.height()).any(|y| {
+        (0..=image.width() - monster.width())
+            .any(|x| pixels.iter().all(|(dx, dy)| image[(x + dx, y + dy)]))
+    })
+}
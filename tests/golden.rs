@@ -0,0 +1,53 @@
+use advent_of_code_2020::challenge::{self, Challenge, Day, Part};
+use advent_of_code_2020::validate;
+use common::fixture_directory;
+
+mod common;
+
+#[test]
+fn solutions_match_fixtures() {
+    let failures = challenge::challenges()
+        .flat_map(|challenge| check(&challenge))
+        .collect::<Vec<_>>();
+
+    assert!(
+        failures.is_empty(),
+        "{} fixtures failed:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+// Day 25 only has a single puzzle
+#[test]
+fn every_day_is_registered() {
+    let last = Challenge::new(Day::Day25, Part::PartB);
+    let expected = Day::all()
+        .flat_map(|day| [Part::PartA, Part::PartB].map(|part| Challenge::new(day, part)))
+        .filter(|challenge| *challenge != last)
+        .collect::<Vec<_>>();
+
+    assert_eq!(challenge::challenges().collect::<Vec<_>>(), expected);
+}
+
+fn check(challenge: &Challenge) -> Vec<String> {
+    let directory = fixture_directory(challenge);
+
+    if !directory.is_dir() {
+        return vec![format!(
+            "{}: missing fixture, expected `<name>.input` and `<name>.{}` in {}",
+            challenge,
+            challenge.part().letter(),
+            directory.display()
+        )];
+    }
+
+    match validate::validate(challenge, &directory) {
+        Ok(outcomes) => outcomes
+            .into_iter()
+            .filter(|(_, outcome)| !outcome.passed())
+            .map(|(name, outcome)| format!("{} [{}]: {}", challenge, name, outcome))
+            .collect(),
+        Err(error) => vec![format!("{}: missing fixture, {:#}", challenge, error)],
+    }
+}
@@ -0,0 +1,182 @@
+use advent_of_code_2020::challenge::{Answer, Challenge, Day};
+use advent_of_code_2020::history;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+// The history file comes from $HISTORY, which is shared by every test in this file
+static HISTORY: Mutex<()> = Mutex::new(());
+
+#[test]
+fn records_round_trip() {
+    with_history("round_trip", |path| {
+        record("01a", "first input", "514579");
+        record("01b", "first input", "241861950");
+        record("01a", "second input", "answer\twith\nbreaks");
+
+        let content = std::fs::read_to_string(path).unwrap();
+        let lines = content.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+
+        let fields = lines[0].split('\t').collect::<Vec<_>>();
+        assert_eq!(fields.len(), 6);
+        assert_eq!(fields[2], "01a");
+        assert_eq!(fields[3].len(), 16, "the input is stored as a hash");
+        assert_eq!(fields[4], "514579");
+        assert_eq!(fields[5], "1500");
+
+        let latest = history::latest_answers(&inputs("first input")).unwrap();
+        assert_eq!(latest.len(), 2);
+        assert_eq!(latest[&challenge("01a")], "514579");
+        assert_eq!(latest[&challenge("01b")], "241861950");
+
+        let latest = history::latest_answers(&inputs("second input")).unwrap();
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[&challenge("01a")], "answer with breaks");
+
+        assert!(history::latest_answers(&BTreeMap::new())
+            .unwrap()
+            .is_empty());
+    });
+}
+
+#[test]
+fn invalid_records_are_reported() {
+    with_history("invalid", |path| {
+        record("01a", "input", "514579");
+
+        let mut content = std::fs::read_to_string(path).unwrap();
+        content.push_str("not a record\n");
+        std::fs::write(path, content).unwrap();
+
+        let error = history::latest_answers(&inputs("input")).unwrap_err();
+        assert_eq!(error.to_string(), "Invalid record on line 2");
+    });
+}
+
+#[test]
+fn unwritable_histories_are_an_error() {
+    with_history("unwritable", |path| {
+        std::fs::create_dir(path).unwrap();
+
+        let answer = Answer {
+            value: "1".to_string(),
+            duration: Duration::ZERO,
+        };
+        assert!(history::record(&challenge("01a"), "input", &answer).is_err());
+    });
+}
+
+#[test]
+fn slowdowns_are_flagged() {
+    with_history("slowdown", |path| {
+        write(path, &[("1", 100), ("1", 100), ("1", 100), ("1", 300)]);
+
+        let report = history::report(&[]).unwrap();
+        let lines = report.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2, "{}", report);
+        assert_eq!(
+            lines[0],
+            "Day 01: Report Repair (Part A): 4 runs, median = 100ns, latest = 300ns (+200%)"
+        );
+        assert!(lines[1].ends_with("300ns is 3.0x slower than the rolling median of 100ns"));
+    });
+}
+
+#[test]
+fn short_windows_are_not_flagged() {
+    with_history("short_window", |path| {
+        write(path, &[("1", 100), ("1", 100), ("1", 300)]);
+
+        let report = history::report(&[]).unwrap();
+        assert_eq!(report.lines().count(), 1, "{}", report);
+    });
+}
+
+#[test]
+fn runs_without_a_duration_are_not_compared() {
+    with_history("zero_median", |path| {
+        write(path, &[("1", 0), ("1", 0), ("1", 0), ("1", 100)]);
+
+        let report = history::report(&[]).unwrap();
+        assert_eq!(
+            report,
+            "Day 01: Report Repair (Part A): 4 runs, median = 0ns, latest = 100ns (n/a)\n"
+        );
+    });
+}
+
+#[test]
+fn changed_answers_are_flagged() {
+    with_history("changed_answer", |path| {
+        write(path, &[("1", 100), ("1", 100), ("2", 100)]);
+
+        let report = history::report(&[]).unwrap();
+        let lines = report.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2, "{}", report);
+        assert!(lines[1].ends_with("answer changed from 1 to 2 for input 0123456789abcdef"));
+    });
+}
+
+#[test]
+fn timestamps_are_utc_dates() {
+    for (timestamp, date) in [
+        (0, "1970-01-01 00:00:00"),
+        (951782400, "2000-02-29 00:00:00"),
+        (1608854400, "2020-12-25 00:00:00"),
+        (1608893999, "2020-12-25 10:59:59"),
+        (4102444799, "2099-12-31 23:59:59"),
+    ] {
+        assert_eq!(history::format_timestamp(timestamp), date);
+    }
+}
+
+fn record(code: &str, input: &str, value: &str) {
+    let answer = Answer {
+        value: value.to_string(),
+        duration: Duration::from_nanos(1500),
+    };
+
+    history::record(&challenge(code), input, &answer).unwrap();
+}
+
+// Runs of day 1 part A on the same input, as answers and durations in nanoseconds
+fn write(path: &PathBuf, runs: &[(&str, u64)]) {
+    let content = runs
+        .iter()
+        .enumerate()
+        .map(|(i, (answer, duration))| {
+            format!(
+                "{}\tabc1234\t01a\t0123456789abcdef\t{}\t{}\n",
+                1608854400 + i,
+                answer,
+                duration
+            )
+        })
+        .collect::<String>();
+
+    std::fs::write(path, content).unwrap();
+}
+
+fn challenge(code: &str) -> Challenge {
+    code.parse().unwrap()
+}
+
+fn inputs(input: &str) -> BTreeMap<Day, String> {
+    [(Day::Day01, input.to_string())].into()
+}
+
+// Points $HISTORY at a file of its own for the duration of a test
+fn with_history(name: &str, test: impl FnOnce(&PathBuf)) {
+    let _lock = HISTORY.lock().unwrap_or_else(|error| error.into_inner());
+
+    let directory = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("history");
+    let path = directory.join(format!("{}.tsv", name));
+    std::fs::create_dir_all(&directory).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let _ = std::fs::remove_dir(&path);
+
+    std::env::set_var("HISTORY", &path);
+    test(&path);
+}
@@ -0,0 +1,92 @@
+#![cfg(unix)]
+
+use advent_of_code_2020::challenge::Challenge;
+use advent_of_code_2020::isolate::{self, Limits, Outcome};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+#[test]
+fn answers_come_back_from_the_child() {
+    let outcome = run("01a", "1721\n979\n366\n299\n675\n1456", Limits::default());
+    assert!(matches!(outcome, Outcome::Solved(answer) if answer.value == "514579"));
+}
+
+#[test]
+fn errors_are_reported() {
+    match run("01a", "1\n2", Limits::default()) {
+        Outcome::Failed(reason) => assert!(reason.contains("Day 01"), "{}", reason),
+        Outcome::Solved(answer) => panic!("solved with {}", answer.value),
+    }
+}
+
+#[test]
+fn memory_is_limited() {
+    let limits = Limits {
+        memory: 64,
+        ..Limits::default()
+    };
+
+    match run("15b", "0,3,6", limits) {
+        Outcome::Failed(reason) => assert!(reason.contains("memory limit"), "{}", reason),
+        Outcome::Solved(answer) => panic!("solved with {}", answer.value),
+    }
+}
+
+#[test]
+fn cpu_time_is_limited() {
+    let limits = Limits {
+        cpu_time: 1,
+        ..Limits::default()
+    };
+
+    // A pocket dimension this large takes over two seconds to simulate, even in a release build
+    let input = (0..200)
+        .map(|y| {
+            (0..200)
+                .map(|x| if (x * 7 + y * 13) % 5 < 2 { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    match run("17b", &input, limits) {
+        Outcome::Failed(reason) => assert!(reason.contains("CPU time limit"), "{}", reason),
+        Outcome::Solved(answer) => panic!("solved with {}", answer.value),
+    }
+}
+
+#[test]
+fn oversized_memory_limits_are_an_error() {
+    let program = Path::new(env!("CARGO_BIN_EXE_advent-of-code-2020"));
+    let challenge: Challenge = "01a".parse().unwrap();
+    let limits = Limits {
+        memory: u64::MAX,
+        ..Limits::default()
+    };
+
+    let error = isolate::isolate(program, &challenge, "", limits)
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("too large"), "{}", error);
+}
+
+// The child flag is internal, so it is not taken for an option among the challenges
+#[test]
+fn child_flag_only_comes_first() {
+    let output = Command::new(env!("CARGO_BIN_EXE_advent-of-code-2020"))
+        .args(["isolate", "01a", "--child", "01a"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("--child"), "{}", stderr);
+}
+
+fn run(code: &str, input: &str, limits: Limits) -> Outcome {
+    let program = Path::new(env!("CARGO_BIN_EXE_advent-of-code-2020"));
+    let challenge: Challenge = code.parse().unwrap();
+
+    isolate::isolate(program, &challenge, input, limits).unwrap()
+}
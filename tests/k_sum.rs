@@ -0,0 +1,79 @@
+use advent_of_code_2020::challenge::{Combination, KSum};
+use proptest::prelude::*;
+
+const EXAMPLE: [u64; 6] = [1721, 979, 366, 299, 675, 1456];
+
+#[test]
+fn example_combinations() {
+    let pair = KSum::new(2, 2020).first(&EXAMPLE).unwrap();
+    assert_eq!(pair.indices, [0, 3]);
+    assert_eq!(pair.values, [1721, 299]);
+    assert_eq!(pair.product(), Some(514579));
+
+    let triple = KSum::new(3, 2020).first(&EXAMPLE).unwrap();
+    assert_eq!(triple.values, [979, 366, 675]);
+    assert_eq!(triple.product(), Some(241861950));
+}
+
+#[test]
+fn duplicates_are_used_as_often_as_they_occur() {
+    assert_eq!(KSum::new(2, 2020).first(&[1010, 1]), None);
+    assert_eq!(
+        KSum::new(2, 2020).all(&[1010, 5, 1010, 1010]),
+        [vec![0, 2], vec![0, 3], vec![2, 3]].map(|indices| Combination {
+            indices,
+            values: vec![1010, 1010]
+        })
+    );
+
+    assert_eq!(KSum::new(3, 6).all(&[2, 2, 2, 2]).len(), 4);
+    assert_eq!(KSum::new(2, 10).all(&[3, 7, 7, 3]).len(), 4);
+}
+
+#[test]
+fn edge_cases() {
+    assert_eq!(KSum::new(0, 0).all(&[1]).len(), 1);
+    assert_eq!(KSum::new(1, 5).all(&[5, 4, 5]).len(), 2);
+    assert_eq!(KSum::new(3, 10).first(&[1, 9]), None);
+    assert_eq!(
+        KSum::new(2, 10).first(&[u64::MAX, 5, 5]).unwrap().indices,
+        [1, 2]
+    );
+
+    let large = 1 << 40;
+    let pair = KSum::new(2, 2 * large).first(&[large, large]).unwrap();
+    assert_eq!(pair.product(), None);
+}
+
+proptest! {
+    #[test]
+    fn all_matches_brute_force(
+        numbers in prop::collection::vec(0u64..20, 0..12),
+        k in 0usize..5,
+        target in 0u64..40,
+    ) {
+        let mut found = KSum::new(k, target)
+            .all(&numbers)
+            .into_iter()
+            .map(|combination| combination.indices)
+            .collect::<Vec<_>>();
+        found.sort();
+
+        prop_assert_eq!(found, brute_force(&numbers, k, target));
+    }
+}
+
+// Every increasing list of k indices whose values add up to the target
+fn brute_force(numbers: &[u64], k: usize, target: u64) -> Vec<Vec<usize>> {
+    (0u32..1 << numbers.len())
+        .filter(|mask| mask.count_ones() as usize == k)
+        .map(|mask| {
+            (0..numbers.len())
+                .filter(|i| mask & (1 << i) != 0)
+                .collect::<Vec<_>>()
+        })
+        .filter(|indices| indices.iter().map(|&i| numbers[i]).sum::<u64>() == target)
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
@@ -0,0 +1,41 @@
+use advent_of_code_2020::challenge::LineIndex;
+
+#[test]
+fn lines_end_with_crlf() {
+    assert_eq!(lines(b"abc\r\ndef\r\n"), ["abc", "def"]);
+    assert_eq!(lines(b"abc\rdef\n"), ["abc\rdef"]);
+}
+
+#[test]
+fn last_line_needs_no_newline() {
+    assert_eq!(lines(b"abc\ndef"), ["abc", "def"]);
+    assert_eq!(lines(b"abc\r\ndef"), ["abc", "def"]);
+    assert_eq!(lines(b"abc\n"), ["abc"]);
+    assert!(lines(b"").is_empty());
+}
+
+#[test]
+fn empty_lines_are_kept() {
+    assert_eq!(lines(b"abc\n\ndef\n"), ["abc", "", "def"]);
+    assert_eq!(lines(b"\r\n\r\nabc\n\n"), ["", "", "abc", ""]);
+}
+
+// Follows the same rules as str::lines
+#[test]
+fn lines_match_str_lines() {
+    for input in ["a\nb", "a\r\n\r\nb\r\n", "\n", "\r\n", "a\r", "\n\na\n\n\n"] {
+        let expected = input.lines().collect::<Vec<_>>();
+        assert_eq!(lines(input.as_bytes()), expected, "{:?}", input);
+    }
+}
+
+fn lines(input: &[u8]) -> Vec<String> {
+    let index = LineIndex::new(input);
+    let lines = index
+        .lines(input)
+        .map(|line| String::from_utf8_lossy(line).into_owned())
+        .collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), index.len());
+    lines
+}
@@ -0,0 +1,31 @@
+use advent_of_code_2020::challenge::{self, Challenge, Day, Part};
+
+// The examples of the puzzle, where the 2020th number is never simply the last starting number
+#[test]
+fn examples_reach_the_2020th_number() {
+    let examples = [
+        ("0,3,6", "436"),
+        ("1,3,2", "1"),
+        ("2,1,3", "10"),
+        ("1,2,3", "27"),
+        ("2,3,1", "78"),
+        ("3,2,1", "438"),
+        ("3,1,2", "1836"),
+    ];
+
+    for (input, expected) in examples {
+        assert_eq!(run(input), expected, "{}", input);
+    }
+}
+
+// Spoken numbers are ages below 2020, so neither large number comes up again and both games play
+// out the same
+#[test]
+fn starting_numbers_can_exceed_the_turns() {
+    assert_eq!(run("0,3,6,1000000"), run("0,3,6,2000000"));
+}
+
+fn run(input: &str) -> String {
+    let challenge = Challenge::new(Day::Day15, Part::PartA);
+    challenge::run(&challenge, input).unwrap().value
+}
@@ -0,0 +1,150 @@
+use advent_of_code_2020::challenge::{Challenge, Part, PassportSchema, ReportFormat};
+use common::fixture_directory;
+
+mod common;
+
+const DRIVING_LICENSES: &str = "
+# A document type which is not a passport
+name required regex [A-Z][a-z]+
+class required one-of A B C
+born required year 1900-2010
+weight optional units 40-200kg 90-440lb
+";
+
+#[test]
+fn parts_are_builtin_schemas() {
+    let challenge: Challenge = "04a".parse().unwrap();
+    let input = std::fs::read_to_string(fixture_directory(&challenge).join("valid.input")).unwrap();
+
+    assert_eq!(PassportSchema::builtin(Part::PartA).count_valid(&input), 4);
+    assert_eq!(PassportSchema::builtin(Part::PartB).count_valid(&input), 4);
+}
+
+#[test]
+fn builtin_schemas_are_files() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/schemas/passports_b.schema");
+    let schema: PassportSchema = std::fs::read_to_string(path).unwrap().parse().unwrap();
+
+    assert_eq!(
+        schema.count_valid("byr:2002 iyr:2010 eyr:2030 hgt:60in hcl:#123abc ecl:brn pid:000000001"),
+        1
+    );
+    assert_eq!(
+        schema.count_valid("byr:2003 iyr:2010 eyr:2030 hgt:60in hcl:#123abc ecl:brn pid:000000001"),
+        0
+    );
+}
+
+#[test]
+fn other_documents_only_need_a_schema() {
+    let schema: PassportSchema = DRIVING_LICENSES.parse().unwrap();
+    let input = "name:Alice class:B\nborn:1980 weight:150lb\n\n\
+                 name:Bob class:A born:1990 eyes:blue\n\n\
+                 name:carol class:C born:2000\n\n\
+                 name:Dave class:D born:1970\n\n\
+                 name:Erin born:1985\n\n\
+                 name:Frank class:A born:1985 weight:250kg\n\n\
+                 name:Grace class:A born:+985";
+
+    // Unknown fields such as eyes are ignored, and weight is optional
+    assert_eq!(schema.count_valid(input), 2);
+}
+
+#[test]
+fn rules_match_whole_values() {
+    let schema: PassportSchema = "id required regex [0-9]{3}\nh required units 1-9cm"
+        .parse()
+        .unwrap();
+
+    assert_eq!(schema.count_valid("id:123 h:9cm"), 1);
+    assert_eq!(schema.count_valid("id:1234 h:9cm"), 0);
+    assert_eq!(schema.count_valid("id:123 h:10cm"), 0);
+    assert_eq!(schema.count_valid("id:123 h:9"), 0);
+    assert_eq!(schema.count_valid("id:123 h:+9cm"), 0);
+}
+
+#[test]
+fn reports_give_every_reason() {
+    let schema = PassportSchema::builtin(Part::PartB);
+    let input = "byr:1937 iyr:2017 cid:147 hgt:183cm\n\
+                 ecl:gry pid:860033327 eyr:2020 hcl:#fffffd\n\
+                 \n\
+                 \n\
+                 hgt:59cm byr:1990 eye:blue\n\
+                 ecl:zzz\n\
+                 \n\
+                 byr:2000 iyr:2015 eyr:2025 hgt:60in hcl:#000000 ecl:oth pid:000000000 eye:x";
+    let report = schema.report(input, ReportFormat::Text);
+
+    assert_eq!(
+        report.lines().collect::<Vec<_>>(),
+        [
+            "Line 1: valid",
+            "Line 5: invalid; missing iyr, eyr, hcl, pid; hgt:59cm violates units 150-193cm \
+             59-76in; ecl:zzz violates one-of amb blu brn gry grn hzl oth; unknown eye",
+            "Line 8: valid; unknown eye",
+            "2 of 3 passports are valid",
+            "Passports per reason:",
+            "  2 unknown eye",
+            "  1 invalid ecl",
+            "  1 invalid hgt",
+            "  1 missing eyr",
+            "  1 missing hcl",
+            "  1 missing iyr",
+            "  1 missing pid",
+        ]
+    );
+}
+
+#[test]
+fn reports_can_be_json() {
+    let schema: PassportSchema = "a required regex [a-z]+".parse().unwrap();
+    let report = schema.report("a:\"x\\\n\nb:1", ReportFormat::Json);
+
+    assert_eq!(
+        report,
+        r#"{
+  "passports": [
+    {"line": 1, "valid": false, "missing": [], "invalid": [{"field": "a", "value": "\"x\\", "rule": "regex [a-z]+"}], "unknown": []},
+    {"line": 3, "valid": false, "missing": ["a"], "invalid": [], "unknown": ["b"]}
+  ],
+  "valid": 0,
+  "total": 2,
+  "reasons": [
+    {"reason": "invalid a", "passports": 1},
+    {"reason": "missing a", "passports": 1},
+    {"reason": "unknown b", "passports": 1}
+  ]
+}
+"#
+    );
+
+    assert_eq!(
+        schema.report("", ReportFormat::Json),
+        "{\n  \"passports\": [],\n  \"valid\": 0,\n  \"total\": 0,\n  \"reasons\": []\n}\n"
+    );
+}
+
+#[test]
+fn schemas_are_checked() {
+    for (schema, error) in [
+        ("", "The schema has no fields"),
+        ("# only a comment", "The schema has no fields"),
+        (
+            "a required\na optional",
+            "Field a is defined more than once",
+        ),
+        ("a\n", "Invalid input on line 1"),
+        ("a required\nb sometimes", "Invalid input on line 2"),
+        ("a required size 1-2", "Invalid input on line 1"),
+        ("a required year", "Invalid input on line 1"),
+        ("a required units 1-2", "Invalid input on line 1"),
+        ("a required year 1", "Invalid input on line 1"),
+        ("a required regex (", "Invalid input on line 1"),
+    ] {
+        match schema.parse::<PassportSchema>() {
+            Ok(_) => panic!("{:?} should be rejected", schema),
+            Err(e) => assert_eq!(e.to_string(), error, "{:?}", schema),
+        }
+    }
+}
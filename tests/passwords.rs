@@ -0,0 +1,115 @@
+use advent_of_code_2020::challenge::{self, Error, Parsing};
+
+const INPUT: &str = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n1-3 a: aB3!aaa";
+
+#[test]
+fn parts_are_policies() {
+    assert_eq!(
+        report("count-in-range").last(),
+        Some(&"2 of 4 passwords are valid".to_string())
+    );
+    assert_eq!(
+        report("positions").last(),
+        Some(&"2 of 4 passwords are valid".to_string())
+    );
+}
+
+#[test]
+fn rejections_name_the_policy() {
+    let report = report("count-in-range and no-repeats=3");
+
+    assert_eq!(report[0], "Line 1, 1-3 a: abcde: valid");
+    assert_eq!(
+        report[1],
+        "Line 2, 1-3 b: cdefg: rejected by count-in-range, 'b' occurs 0 times, expecting 1 to 3"
+    );
+    assert_eq!(
+        report[2],
+        "Line 3, 2-9 c: ccccccccc: rejected by no-repeats=3, 'c' repeats 9 times in a row, \
+         allowing at most 3"
+    );
+}
+
+#[test]
+fn rejections_count_every_occurrence() {
+    let report = challenge::password_report("1-3 a: aaaaaaaaaa", "count-in-range", Parsing::Strict);
+
+    assert!(report.unwrap().starts_with(
+        "Line 1, 1-3 a: aaaaaaaaaa: rejected by count-in-range, 'a' occurs 10 times, expecting 1 \
+         to 3\n"
+    ));
+}
+
+#[test]
+fn and_binds_tighter_than_or() {
+    let report = report("min-length=6 and classes=4 or positions");
+
+    assert!(report[0].ends_with("valid"));
+    assert!(report[1].contains(
+        "rejected by min-length=6, 5 characters long, expecting at least 6 and by positions"
+    ));
+    assert!(report[3].ends_with("valid"));
+}
+
+#[test]
+fn policies_are_checked() {
+    for policy in [
+        "",
+        "and",
+        "positions or",
+        "unknown",
+        "positions=2",
+        "min-length=x",
+    ] {
+        assert!(
+            challenge::password_report(INPUT, policy, Parsing::Strict).is_err(),
+            "{:?}",
+            policy
+        );
+    }
+}
+
+#[test]
+fn strict_parsing_lists_every_malformed_line() {
+    let input = format!("{}\n1-3 ab: x\n\n4 a: aaaa", INPUT);
+    let error = challenge::password_report(&input, "positions", Parsing::Strict).unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "3 malformed lines\n  \
+         line 5: Expected a single letter, found \"ab\"\n  \
+         line 6: Expected \"-\" in \"\"\n  \
+         line 7: Expected \"-\" in \"4 a: aaaa\""
+    );
+
+    let challenge = "02a".parse().unwrap();
+    match challenge::run(&challenge, &input) {
+        Err(Error::Solver { source, .. }) => {
+            assert!(source
+                .to_string()
+                .contains("passwords --lenient count-in-range"));
+            assert!(format!("{:#}", source).contains("3 malformed lines"));
+        }
+        _ => panic!("{} should reject malformed lines", challenge),
+    }
+}
+
+#[test]
+fn lenient_parsing_lists_skipped_lines() {
+    let input = format!("{}\nbad", INPUT);
+    let report = challenge::password_report(&input, "positions", Parsing::Lenient).unwrap();
+
+    assert!(report.ends_with(
+        "2 of 4 passwords are valid\n\
+         Skipped 1 malformed line\n  \
+         line 5: Expected \"-\" in \"bad\"\n"
+    ));
+}
+
+fn report(policy: &str) -> Vec<String> {
+    challenge::password_report(INPUT, policy, Parsing::Strict)
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
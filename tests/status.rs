@@ -0,0 +1,81 @@
+use advent_of_code_2020::challenge::{Challenge, Day, Part};
+use advent_of_code_2020::status::{self, Account};
+use std::collections::BTreeMap;
+
+#[test]
+fn calendar_shows_earned_stars() {
+    let html = r#"
+        <a aria-label="Day 1, two stars" href="/2020/day/1" class="calendar-day1 calendar-verycomplete">
+        <a aria-label="Day 2, one star" href="/2020/day/2" class="calendar-day2 calendar-complete">
+        <a aria-label="Day 3" href="/2020/day/3" class="calendar-day3">
+        <span class="calendar-day4">
+    "#;
+
+    assert_eq!(
+        status::parse_calendar(html),
+        ["01a", "01b", "02a"].map(challenge)
+    );
+}
+
+#[test]
+fn accepted_answers_follow_the_parts() {
+    let html = "<p>Your puzzle answer was <code>514579</code>.</p>\
+                <p>Your puzzle answer was <code>241861950</code>.</p>";
+
+    let answers = status::parse_answers(Day::Day01, html);
+
+    assert_eq!(answers[&challenge("01a")], "514579");
+    assert_eq!(answers[&challenge("01b")], "241861950");
+}
+
+#[test]
+fn answers_are_verified_against_the_history() {
+    let account = Account {
+        earned: ["01a", "01b"].map(challenge).to_vec(),
+        answers: [("01a", "1"), ("01b", "2")]
+            .map(|(code, answer)| (challenge(code), answer.to_string()))
+            .into(),
+        ..Account::default()
+    };
+    let history = [("01a", "1"), ("01b", "3")]
+        .map(|(code, answer)| (challenge(code), answer.to_string()))
+        .into();
+
+    let stars = status::stars(&account, &history);
+
+    assert!(stars[&challenge("01a")].verified);
+    assert!(!stars[&challenge("01b")].verified);
+    assert!(stars[&challenge("01b")].earned);
+    assert!(!stars[&challenge("02a")].earned);
+    assert!(stars[&challenge("02a")].implemented);
+}
+
+#[test]
+fn last_star_comes_with_the_others() {
+    let all = Day::all()
+        .flat_map(|day| [Part::PartA, Part::PartB].map(|part| Challenge::new(day, part)))
+        .collect::<Vec<_>>();
+    let last = challenge("25b");
+
+    let mut account = Account {
+        earned: all[..all.len() - 2].to_vec(),
+        ..Account::default()
+    };
+    assert_eq!(
+        status::stars(&account, &BTreeMap::new())[&last],
+        Default::default()
+    );
+
+    account.earned.push(challenge("25a"));
+    let stars = status::stars(&account, &BTreeMap::new());
+    assert!(stars[&last].earned);
+    assert!(stars[&last].implemented && stars[&last].verified);
+
+    let calendar = status::calendar(&stars);
+    assert!(calendar.contains("25 +*"), "{}", calendar);
+    assert!(calendar.contains("50 of 50 stars earned"), "{}", calendar);
+}
+
+fn challenge(code: &str) -> Challenge {
+    code.parse().unwrap()
+}
@@ -0,0 +1,92 @@
+use advent_of_code_2020::challenge::{self, Challenge, Error, Slope, TobogganMap};
+use common::fixture_directory;
+
+mod common;
+
+#[test]
+fn maps_can_be_wider_than_a_word() {
+    // Trees on the diagonal of slope 3, which only wraps around after 100 columns
+    let map = (0..40)
+        .map(|y| {
+            let mut row = vec!['.'; 100];
+            row[y * 3 % 100] = '#';
+            row.into_iter().collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    assert_eq!(run("03a", &map), "40");
+}
+
+#[test]
+fn rows_have_to_be_the_same_width() {
+    let challenge: Challenge = "03a".parse().unwrap();
+    match challenge::run(&challenge, "..#\n.#\n#..") {
+        Err(Error::Solver { source, .. }) => {
+            assert_eq!(source.to_string(), "Row 2 has 2 columns, expected 3")
+        }
+        _ => panic!("ragged rows should fail"),
+    }
+}
+
+#[test]
+fn any_slopes() {
+    let map = example();
+    let slopes = ["1,1", "3,1", "5,1", "7,1", "1,2"].map(slope);
+
+    assert_eq!(slopes.map(|slope| map.trees(slope)), [2, 7, 3, 4, 2]);
+    assert_eq!(map.product(&slopes).unwrap(), 336);
+
+    // The example is 11 columns wide, so a step of 12 lands where a step of 1 does
+    assert_eq!(map.trees(slope("12,1")), map.trees(slope("1,1")));
+    assert!("1,0".parse::<Slope>().is_err());
+}
+
+#[test]
+fn products_which_overflow_are_an_error() {
+    // Every row is a tree, so a straight line down hits 1000 of them
+    let map = vec!["#"; 1000].join("\n").parse::<TobogganMap>().unwrap();
+    let slopes = [slope("0,1"); 7];
+
+    assert_eq!(
+        map.product(&slopes).unwrap_err().to_string(),
+        "The product of the trees is too large"
+    );
+}
+
+#[test]
+fn slope_search() {
+    let slopes = example().search(3);
+
+    assert_eq!(slopes.len(), 12);
+    assert_eq!(slopes.iter().map(|&(_, trees)| trees).max(), Some(7));
+    assert!(slopes.contains(&(slope("0,1"), 3)));
+}
+
+#[test]
+fn path_is_drawn_over_the_map() {
+    let render = example().render(slope("3,1"));
+    let lines = render.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines[0], "X.##.........##.........##.......");
+    assert_eq!(lines[10], ".#..#...#.#.#..#...#.#.#..#...O.#");
+    assert_eq!(render.matches('O').count(), 7);
+    assert_eq!(render.matches('X').count(), 4);
+}
+
+fn example() -> TobogganMap {
+    let directory = fixture_directory(&"03a".parse().unwrap());
+    std::fs::read_to_string(directory.join("example.input"))
+        .unwrap()
+        .parse()
+        .unwrap()
+}
+
+fn slope(slope: &str) -> Slope {
+    slope.parse().unwrap()
+}
+
+fn run(code: &str, input: &str) -> String {
+    let challenge: Challenge = code.parse().unwrap();
+    challenge::run(&challenge, input).unwrap().value
+}
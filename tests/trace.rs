@@ -0,0 +1,29 @@
+use advent_of_code_2020::challenge::{self, Challenge};
+use advent_of_code_2020::trace;
+use std::path::PathBuf;
+
+// The subscriber is global, so everything runs within a single test
+#[test]
+fn spans_are_written_to_the_trace() {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("trace.json");
+    let guard = trace::chrome(path.to_str().unwrap()).unwrap();
+
+    let challenge: Challenge = "01a".parse().unwrap();
+    challenge::run(&challenge, "1721\n979\n366\n299\n675\n1456").unwrap();
+    drop(guard);
+
+    let trace = std::fs::read_to_string(&path).unwrap();
+
+    for span in ["run", "split_lines", "solver"] {
+        assert!(
+            trace.contains(&format!("\"name\":\"{}\"", span)),
+            "{} is missing from {}",
+            span,
+            trace
+        );
+    }
+
+    assert!(trace.contains("\"function\":\"advent_of_code_2020::challenge::day_01::part_a\""));
+    assert!(trace.contains("\"challenge\":\"Day 01: Report Repair (Part A)\""));
+    assert!(trace.trim_end().ends_with(']'));
+}
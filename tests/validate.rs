@@ -0,0 +1,137 @@
+use advent_of_code_2020::challenge::Challenge;
+use advent_of_code_2020::validate::{self, Outcome};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const EXPENSES: &str = "1721\n979\n366\n299\n675\n1456\n";
+
+#[test]
+fn matching_answers_pass() {
+    let directory = input_directory("matching");
+    write(&directory, "example.input", EXPENSES);
+    write(&directory, "example.a", "514579\n");
+
+    let outcomes = validate::validate(&challenge("01a"), &directory).unwrap();
+
+    assert_eq!(outcomes.len(), 1);
+    assert_eq!(outcomes[0].0, "example");
+    assert!(outcomes[0].1.passed());
+}
+
+#[test]
+fn mismatches_report_both_answers() {
+    let directory = input_directory("mismatch");
+    write(&directory, "example.input", EXPENSES);
+    write(&directory, "example.a", "42");
+
+    let outcomes = validate::validate(&challenge("01a"), &directory).unwrap();
+
+    match &outcomes[0].1 {
+        Outcome::Failed { expected, actual } => {
+            assert_eq!((expected.as_str(), actual.as_str()), ("42", "514579"))
+        }
+        outcome => panic!("expected a mismatch, got {}", outcome),
+    }
+    assert_eq!(
+        outcomes[0].1.to_string(),
+        "FAILED, expected 42 but got 514579"
+    );
+}
+
+#[test]
+fn errors_are_reported() {
+    let directory = input_directory("errors");
+    write(&directory, "bad.input", "1721\nabc\n");
+    write(&directory, "bad.a", "514579");
+
+    let outcomes = validate::validate(&challenge("01a"), &directory).unwrap();
+
+    assert!(matches!(outcomes[0].1, Outcome::Errored(_)));
+    assert!(outcomes[0].1.to_string().starts_with("ERROR, "));
+}
+
+#[test]
+fn panics_are_reported_and_the_hook_restored() {
+    static HOOK_CALLED: AtomicBool = AtomicBool::new(false);
+
+    let directory = input_directory("panics");
+    write(&directory, "example.input", EXPENSES);
+    write(&directory, "example.a", "514579");
+
+    std::panic::set_hook(Box::new(|_| HOOK_CALLED.store(true, Ordering::SeqCst)));
+
+    let outcomes = validate::quietly(|| {
+        validate::validate_with(&challenge("01a"), &directory, |_, input| {
+            panic!("cannot solve {} lines", input.lines().count())
+        })
+    })
+    .unwrap();
+
+    assert!(
+        !HOOK_CALLED.load(Ordering::SeqCst),
+        "the panic was reported"
+    );
+    assert!(matches!(outcomes[0].1, Outcome::Panicked(_)));
+    assert_eq!(outcomes[0].1.to_string(), "PANICKED, cannot solve 6 lines");
+
+    let _ = std::panic::catch_unwind(|| panic!("after validating"));
+    let _ = std::panic::take_hook();
+    assert!(
+        HOOK_CALLED.load(Ordering::SeqCst),
+        "the hook was not restored"
+    );
+}
+
+#[test]
+fn inputs_without_an_answer_are_skipped() {
+    let directory = input_directory("skipped");
+    write(&directory, "answered.input", EXPENSES);
+    write(&directory, "answered.b", "241861950");
+    write(&directory, "unanswered.input", EXPENSES);
+    write(&directory, "unanswered.a", "514579");
+
+    let outcomes = validate::validate(&challenge("01b"), &directory).unwrap();
+    let names = outcomes
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+
+    assert_eq!(names, ["answered"]);
+}
+
+#[test]
+fn missing_answers_are_an_error() {
+    let directory = input_directory("missing");
+    write(&directory, "example.input", EXPENSES);
+
+    let Err(error) = validate::validate(&challenge("01a"), &directory) else {
+        panic!("an input without answers should not be validated");
+    };
+
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "Cannot find any inputs with expected answers for Day 01: Report Repair (Part A) in {}",
+            directory.display()
+        )
+    );
+}
+
+fn challenge(code: &str) -> Challenge {
+    code.parse().unwrap()
+}
+
+// An empty directory for the inputs of one test
+fn input_directory(name: &str) -> PathBuf {
+    let directory = PathBuf::from(env!("CARGO_TARGET_TMPDIR"))
+        .join("validate")
+        .join(name);
+
+    let _ = std::fs::remove_dir_all(&directory);
+    std::fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+fn write(directory: &Path, name: &str, contents: &str) {
+    std::fs::write(directory.join(name), contents).unwrap();
+}
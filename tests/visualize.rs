@@ -0,0 +1,58 @@
+use advent_of_code_2020::challenge::{self, Challenge, Simulation};
+use advent_of_code_2020::generate;
+
+#[test]
+fn simulations_end_with_the_answer() {
+    for code in ["11a", "11b", "17a", "17b", "24b"] {
+        let challenge: Challenge = code.parse().unwrap();
+        let input = generate::generate(challenge.day(), 12, 3);
+
+        let mut simulation = challenge::simulation(&challenge, &input).unwrap();
+        let answer = challenge::run(&challenge, &input).unwrap();
+
+        assert_eq!(
+            run_to_end(simulation.as_mut()).to_string(),
+            answer.value,
+            "{}",
+            challenge
+        );
+    }
+}
+
+#[test]
+fn cups_end_with_the_answer() {
+    let challenge = "23a".parse().unwrap();
+    let mut simulation = challenge::simulation(&challenge, "389125467").unwrap();
+
+    while simulation.step() {}
+
+    assert_eq!(
+        simulation.status(),
+        "move 100 of 100, cups after 1: 6 7 3 8 4 5 2 9"
+    );
+}
+
+#[test]
+fn other_days_cannot_be_visualized() {
+    let challenge = "01a".parse().unwrap();
+    assert!(challenge::simulation(&challenge, "").is_err());
+}
+
+// Active cells of every slice once the simulation is over, which is what the day counts
+fn run_to_end(simulation: &mut dyn Simulation) -> usize {
+    while simulation.step() {}
+
+    let slices = challenge::every_slice(&simulation.slices());
+
+    slices
+        .iter()
+        .map(|slice| {
+            let frame = simulation.render(slice);
+            frame
+                .cells()
+                .iter()
+                .filter(|cell| cell.symbol == '#')
+                .count()
+        })
+        .sum()
+}